@@ -0,0 +1,70 @@
+use crate::maze::Maze;
+use crate::node::Position;
+
+/// Union-find over walkable fields of the maze, used for answering reachability queries.
+///
+/// Opening a field only merges components, so it is applied incrementally.
+/// Closing a field might split a component, in that case the structure has to be rebuilt.
+pub(crate) struct Connectivity {
+    width: usize,
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Connectivity {
+    /// Builds connectivity of all walkable fields of the `maze`.
+    pub(crate) fn new(maze: &Maze) -> Self {
+        let width = maze.field().iter().map(Vec::len).max().unwrap_or(0);
+        let len = width * maze.y_len();
+        let mut connectivity = Connectivity {
+            width,
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        };
+
+        for (y, row) in maze.field().iter().enumerate() {
+            for x in 0..row.len() {
+                connectivity.open(maze, Position((x, y)));
+            }
+        }
+        connectivity
+    }
+
+    /// Merges the field at `position` with all of its walkable neighbours.
+    pub(crate) fn open(&mut self, maze: &Maze, position: Position) {
+        if !maze.is_walkable(position) {
+            return;
+        }
+        for neighbour in maze.connections(position) {
+            self.union(self.index(position), self.index(neighbour));
+        }
+    }
+
+    /// Returns `true` if both positions belong to the same component.
+    pub(crate) fn connected(&self, a: Position, b: Position) -> bool {
+        self.root(self.index(a)) == self.root(self.index(b))
+    }
+
+    fn index(&self, position: Position) -> usize {
+        position.0 .1 * self.width + position.0 .0
+    }
+
+    fn root(&self, mut index: usize) -> usize {
+        while self.parent[index] != index {
+            index = self.parent[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.root(a), self.root(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
@@ -0,0 +1,104 @@
+/// [`Direction`] represents one of the 8 directions of movement inside the maze.
+///
+/// Directions are ordered clockwise starting from `North`, `y` axis grows downwards (towards `South`).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// All directions, in the same order as their [`index`](Direction::index).
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Returns index of the direction (`North` is `0`, increasing clockwise).
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns `(x, y)` offset of a single step in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        use Direction::*;
+        match *self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (-1, -1),
+        }
+    }
+
+    /// Returns [`Direction`] matching the `(x, y)` offset of a single step, if there is one.
+    pub fn from_offset(offset: (isize, isize)) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    /// Returns arrow symbol pointing in this direction.
+    pub fn arrow(&self) -> char {
+        ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'][self.index()]
+    }
+
+    /// Returns `true` if the direction is diagonal.
+    pub fn is_diagonal(&self) -> bool {
+        let (x, y) = self.offset();
+        x != 0 && y != 0
+    }
+
+    /// Returns the turn from this direction to `to`, as a number of 45 degree steps.
+    ///
+    /// Positive values turn clockwise, result is in range `-3..=4` (a U-turn is always `4`).
+    pub fn turn(&self, to: Direction) -> isize {
+        let steps = (to.index() as isize - self.index() as isize).rem_euclid(8);
+        if steps > 4 {
+            steps - 8
+        } else {
+            steps
+        }
+    }
+}
+
+/// Directions the path can move in, see [`crate::Maze::set_movement`].
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Movement {
+    /// Only north, east, south and west.
+    FourWay,
+    /// All 8 directions, diagonal ones included. This is the default.
+    #[default]
+    EightWay,
+}
+
+impl Movement {
+    /// Returns `true` if a step in `direction` is allowed.
+    pub(crate) fn allows(&self, direction: Direction) -> bool {
+        *self == Movement::EightWay || !direction.is_diagonal()
+    }
+}
+
+/// Command for a robot that can only turn in place and move forward, see [`crate::Maze::get_turtle_commands`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurtleCmd {
+    /// Turn in place by the given number of degrees, positive values turn clockwise.
+    Turn(isize),
+    /// Move forward onto the next field in the current heading.
+    Forward,
+}
@@ -27,6 +27,17 @@ pub(crate) enum ErrorKind {
     MazeNotSolved,
     MazeIsNotSet,
     StartEndNotSet,
+    InvalidCostFile,
+    InvalidGrid,
+    InvalidElevation,
+    ElevationNotSet,
+    InvalidPath,
+    PositionOutOfBounds,
+    PositionNotWalkable,
+    Cancelled,
+    InadmissibleHeuristic,
+    DimensionMismatch,
+    TooManyFields,
 }
 
 impl ErrorKind {
@@ -34,11 +45,24 @@ impl ErrorKind {
         use ErrorKind::*;
         match *self {
             InvalidFilePath => "Invalid file path",
-            InvalidCharacters => "Characters are not unique. (start, end, wall...)",
+            InvalidCharacters => {
+                "Characters are not unique (start, end, wall...) or separator is used as a field."
+            }
             MazeIsNotSet => "Maze is not set (loaded), consider using `set` method on `Maze`.",
             MazeIsNotSolvable => "This maze is unsolvable.",
             MazeNotSolved => "Could not retrieve path, maze is not yet solved.",
             StartEndNotSet => "Start/End are not set.",
+            InvalidCostFile => "Cost grid must match the maze dimensions (digits only in files).",
+            InvalidGrid => "Maze must not be empty and all rows must be the same length.",
+            InvalidElevation => "Elevation grid must match the maze dimensions.",
+            ElevationNotSet => "Elevation is not set, consider using `set_elevation` method.",
+            InvalidPath => "Path contains a field that can't be entered from the previous one.",
+            PositionOutOfBounds => "Position is outside of the maze.",
+            PositionNotWalkable => "Field at the position is not walkable.",
+            Cancelled => "Search was cancelled.",
+            InadmissibleHeuristic => "Heuristic overestimates the remaining cost of the path.",
+            DimensionMismatch => "Mazes must have the same dimensions.",
+            TooManyFields => "Maze has too many walkable fields for this operation.",
         }
     }
 }
@@ -1,3 +1,5 @@
+pub(crate) mod connectivity;
+pub(crate) mod direction;
 pub(crate) mod error;
 /// This crate provides basic API for solving 2D mazes inside a text file.
 ///
@@ -6,13 +8,20 @@ pub(crate) mod error;
 ///
 /// Maze is directly loaded from text file and is solved using Euclidian Distance heuristic.
 ///
-/// It uses 8 directions of movement.
+/// It uses 8 directions of movement by default, 4 directional movement can be selected with `Maze::set_movement`.
 ///
 /// For now it only contains **A*** (A-star) pathfinder and it is guaranteed to find the shortest possible path.
 ///
 /// *Note:* *It also uses `extern` crate `priority_queue`.*
 pub(crate) mod maze;
 pub(crate) mod node;
+pub(crate) mod random;
+pub(crate) mod search;
+pub(crate) mod sparse;
 
-pub use maze::Maze;
+pub use direction::{Direction, Movement, TurtleCmd};
 pub use error::Error;
+pub use maze::{MarkerMode, Maze, MazeConfig, Order, PathMetrics};
+pub use node::{Heuristic, NodeInfo};
+pub use search::{astar_grid, Algorithm, QueueImpl, SolveProgress, SolveState, SolveStats};
+pub use sparse::SparseMaze;
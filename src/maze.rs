@@ -1,10 +1,22 @@
 #![allow(dead_code)]
+use crate::connectivity::Connectivity;
+use crate::direction::{Direction, Movement, TurtleCmd};
 use crate::error::{Error, ErrorKind::*};
-use crate::node::{Node, Position, Priority};
-use priority_queue::PriorityQueue;
-use std::collections::{HashSet, VecDeque};
+use crate::node::{Heuristic, Node, Position, TieBreak, Tiebreaks};
+use crate::random::Rng;
+use crate::search::{
+    self, Algorithm, Grid, Limits, Outcome, QueueImpl, SolveProgress, SolveState, SolveStats,
+};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::default::Default;
+use std::fmt::Display;
 use std::fs;
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
 
 // Colours.
 const PATH_COLOUR: &str = "\x1B[92m";
@@ -12,9 +24,27 @@ const WALL_COLOUR: &str = "\x1B[91m";
 const START_COLOUR: &str = "\x1B[1;93m";
 const END_COLOUR: &str = "\x1B[1;93m";
 const RESET: &str = "\x1B[0m";
+// Background colours, used by half-block rendering when both halves of a character are filled.
+const PATH_BACKGROUND: &str = "\x1B[102m";
+const WALL_BACKGROUND: &str = "\x1B[101m";
+
+/// Symbols tried in order when `path_char` is already used by a field of the maze.
+const PATH_CHAR_FALLBACKS: [char; 8] = ['*', '+', 'o', '@', '#', '%', '&', '~'];
+
+/// Most walkable fields `all_solvable_pairs` lists pairs of, the number of pairs grows quadratically.
+const MAX_PAIR_FIELDS: usize = 1024;
+
+/// Fixed point scale of costs searched in float mode, cost of `1.0` is searched as this value.
+const FLOAT_SCALE: f64 = 1_000_000.0;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Coordinates `(x, y)` of a field inside the maze.
+type Field = (usize, usize);
+
+/// Custom cost of a step between two neighbouring fields, see [`Maze::set_cost_fn`].
+pub(crate) type CostFn = dyn Fn((usize, usize), (usize, usize)) -> Option<usize>;
+
 /// `Path` is wrapper around the shortest path of the maze.
 ///
 /// Shortest path is represented as a `VecDeque` of a tuple (`usize, usize`) elements that represent coordinates.
@@ -22,6 +52,76 @@ struct Path {
     fields: VecDeque<(usize, usize)>,
 }
 
+/// Order of coordinates inside of the tuples returned by [`Maze::get_path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// `(row, column)`, that is `(y, x)`.
+    RowCol,
+    /// `(column, row)`, that is `(x, y)`. This is the default.
+    #[default]
+    ColRow,
+}
+
+/// How `start` and `end` markers relate to the field they are placed on, see [`Maze::set_marker_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerMode {
+    /// Markers lie on open floor, their fields are part of the path. This is the default.
+    #[default]
+    Overlay,
+    /// Markers are fields of their own, the path leads between them and is never drawn over them.
+    Distinct,
+}
+
+/// Symbols and movement of a [`Maze`] set all at once by [`Maze::from_grid_with_config`].
+///
+/// Default values are the same as for [`Maze::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MazeConfig {
+    /// Symbol of the start field.
+    pub start_char: char,
+    /// Symbol of the end field.
+    pub end_char: char,
+    /// Symbol of walls.
+    pub wall_char: char,
+    /// Symbol the path is drawn with.
+    pub path_char: char,
+    /// Symbol that marks start of a new row inside the text file.
+    pub separator: char,
+    /// Directions the path can move in.
+    pub movement: Movement,
+    /// Heuristic estimating the remaining cost of the path.
+    pub heuristic: Heuristic,
+}
+
+impl Default for MazeConfig {
+    fn default() -> Self {
+        MazeConfig {
+            start_char: 'S',
+            end_char: 'E',
+            wall_char: 'W',
+            path_char: 'X',
+            separator: '\\',
+            movement: Movement::default(),
+            heuristic: Heuristic::default(),
+        }
+    }
+}
+
+/// Composition and cost of the shortest path, returned by [`Maze::path_metrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathMetrics {
+    /// Number of fields on the path, including start and end unless markers are [`MarkerMode::Distinct`].
+    pub cells: usize,
+    /// Number of moves between fields, `straight + diagonal` plus any portal jumps.
+    pub moves: usize,
+    /// Number of horizontal or vertical moves, each costs `10`.
+    pub straight: usize,
+    /// Number of diagonal moves, each costs `14`.
+    pub diagonal: usize,
+    /// Total cost of the path, including entry and climbing costs.
+    pub cost: usize,
+}
+
 /// [`Maze`] is a core type of this crate with basic API for customizing start, end, separator and wall symbols and some other accessories.
 ///
 /// Once constructed it can give out basic information of our maze parameters, and can parse any `.txt` file.
@@ -30,11 +130,42 @@ pub struct Maze {
     start: Option<Position>,
     pub(crate) end: Option<Position>,
     path: Option<Path>,
+    costs: Option<Vec<Vec<usize>>>,
+    elevation: Option<Vec<Vec<i32>>>,
+    capacities: Vec<(Position, usize)>,
+    portals: Vec<(Position, Position, usize)>,
     start_char: char,
     end_char: char,
     wall_char: char,
     path_char: char,
     separator: char,
+    show_legend: bool,
+    strip_ansi: bool,
+    path_over_markers: bool,
+    marker_mode: MarkerMode,
+    connectivity: Option<Connectivity>,
+    tiebreak: Option<Box<TieBreak>>,
+    cost_fn: Option<Box<CostFn>>,
+    order: Order,
+    heuristic: Heuristic,
+    movement: Movement,
+    solid_border: bool,
+    wall_inflation: usize,
+    comment_prefix: Option<char>,
+    blank_line_fill: Option<char>,
+    cross_tiebreak: bool,
+    arrow_path: bool,
+    path_cache: Option<HashMap<(Field, Field), Vec<Field>>>,
+    avoid: HashSet<Position>,
+    thin_walls: HashSet<(Field, Field)>,
+    turn_restrictions: HashSet<(Position, Direction, Direction)>,
+    float_costs: bool,
+    queue: QueueImpl,
+    resolution: usize,
+    stats: Option<SolveStats>,
+    validate_admissibility: bool,
+    endpoints_plain: bool,
+    snap_endpoints: bool,
 }
 
 impl Maze {
@@ -55,10 +186,10 @@ impl Maze {
     ///
     /// # Examples
     /// ```no_run
-    /// fn main() -> Result<(), Error> {
-    ///     let mut maze = Maze::new();
+    /// use astar::{Error, Maze};
     ///
-    ///     maze.set("maze.txt")?;
+    /// fn main() -> Result<(), Error> {
+    ///     let maze = Maze::new().set("maze.txt")?;
     ///
     ///     let maze = maze
     ///         .set_walls_char('O')
@@ -74,12 +205,207 @@ impl Maze {
             start: None,
             end: None,
             path: None,
+            costs: None,
+            elevation: None,
+            capacities: Vec::new(),
+            portals: Vec::new(),
             start_char: 'S',
             end_char: 'E',
             wall_char: 'W',
             path_char: 'X',
             separator: '\\',
+            show_legend: false,
+            strip_ansi: false,
+            path_over_markers: false,
+            marker_mode: MarkerMode::Overlay,
+            connectivity: None,
+            tiebreak: None,
+            cost_fn: None,
+            order: Order::ColRow,
+            heuristic: Heuristic::Euclidean,
+            movement: Movement::EightWay,
+            solid_border: false,
+            wall_inflation: 0,
+            comment_prefix: None,
+            blank_line_fill: None,
+            cross_tiebreak: false,
+            arrow_path: false,
+            path_cache: None,
+            avoid: HashSet::new(),
+            thin_walls: HashSet::new(),
+            turn_restrictions: HashSet::new(),
+            float_costs: false,
+            queue: QueueImpl::Binary,
+            resolution: 1,
+            stats: None,
+            validate_admissibility: false,
+            endpoints_plain: false,
+            snap_endpoints: false,
+        }
+    }
+
+    /// Constructs a [`Maze`] with default symbols from its rows.
+    ///
+    /// # Errors
+    /// If there are no rows or they are not the same length.
+    ///
+    /// If the separator is used as a field.
+    ///
+    /// # Examples
+    /// ```
+    /// use astar::{Error, Maze};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut maze = Maze::from_lines(&["S..", "WW.", "..E"])?;
+    ///
+    ///     maze.try_solve()?;
+    ///     assert_eq!(maze.get_path()?, vec![(0, 0), (1, 0), (2, 1), (2, 2)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_lines(lines: &[&str]) -> Result<Self> {
+        let maze = lines
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect::<Vec<Vec<char>>>();
+
+        Maze::new().set_grid(maze)
+    }
+
+    /// Constructs a [`Maze`] with default symbols where each field is a wall with probability `density`,
+    /// other fields are open (`'.'`). Start is placed in the top left corner and end in the bottom right one.
+    ///
+    /// The same `seed` always produces the same maze, it is not guaranteed to be solvable (see `is_solvable`).
+    /// `width` and `height` of `0` are treated as `1`, in a maze with a single field it holds the end.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let open = Maze::random_obstacles(6, 4, 0.0, 1);
+    /// assert!(open.field().iter().flatten().all(|field| *field != open.wall()));
+    ///
+    /// let walled = Maze::random_obstacles(6, 4, 1.0, 1);
+    /// let walls = walled.field().iter().flatten().filter(|field| **field == walled.wall());
+    /// assert_eq!(walls.count(), 6 * 4 - 2);
+    /// ```
+    pub fn random_obstacles(width: usize, height: usize, density: f64, seed: u64) -> Self {
+        let mut maze = Maze::new();
+        let mut rng = Rng::new(seed);
+        let (width, height) = (width.max(1), height.max(1));
+
+        let mut field = || {
+            if rng.next_f64() < density {
+                maze.wall_char
+            } else {
+                '.'
+            }
+        };
+        let grid = (0..height)
+            .map(|_| (0..width).map(|_| field()).collect())
+            .collect();
+
+        maze.maze = grid;
+        maze.maze[0][0] = maze.start_char;
+        maze.maze[height - 1][width - 1] = maze.end_char;
+
+        maze.calculate_start();
+        maze.calculate_end();
+        maze.update_connectivity();
+        maze
+    }
+
+    /// Constructs a [`Maze`] from an already parsed grid, with symbols, movement and heuristic taken from `config`.
+    ///
+    /// # Errors
+    /// If `start`, `end`, `separator` or `wall` share the same character.
+    ///
+    /// If the grid is empty or its rows are not the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use astar::{Maze, MazeConfig};
+    ///
+    /// let grid = vec![vec!['a', '.', '.'], vec!['#', '#', '.'], vec!['.', '.', 'b']];
+    /// let config = MazeConfig {
+    ///     start_char: 'a',
+    ///     end_char: 'b',
+    ///     wall_char: '#',
+    ///     ..MazeConfig::default()
+    /// };
+    ///
+    /// let mut configured = Maze::from_grid_with_config(grid.clone(), config).unwrap();
+    /// let mut chained = Maze::try_from(grid)
+    ///     .unwrap()
+    ///     .set_start_char('a')
+    ///     .set_end_char('b')
+    ///     .set_walls_char('#');
+    ///
+    /// configured.try_solve().unwrap();
+    /// chained.try_solve().unwrap();
+    /// assert_eq!(configured.get_path().unwrap(), chained.get_path().unwrap());
+    /// ```
+    ///
+    /// Movement and heuristic are configured as well.
+    /// ```
+    /// use astar::{Heuristic, Maze, MazeConfig, Movement};
+    ///
+    /// let config = MazeConfig {
+    ///     movement: Movement::FourWay,
+    ///     heuristic: Heuristic::Manhattan,
+    ///     ..MazeConfig::default()
+    /// };
+    /// let grid = vec![vec!['S', '.'], vec!['.', 'E']];
+    /// let mut maze = Maze::from_grid_with_config(grid, config).unwrap();
+    ///
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 3);
+    /// assert_eq!(maze.get_path_cost().unwrap(), 20.0);
+    /// ```
+    pub fn from_grid_with_config(grid: Vec<Vec<char>>, config: MazeConfig) -> Result<Self> {
+        let mut maze = Maze::new();
+        maze.start_char = config.start_char;
+        maze.end_char = config.end_char;
+        maze.wall_char = config.wall_char;
+        maze.path_char = config.path_char;
+        maze.separator = config.separator;
+        maze.movement = config.movement;
+        maze.heuristic = config.heuristic;
+
+        if maze.are_chars_invalid() {
+            return Err(InvalidCharacters.into());
         }
+        maze.set_grid(grid)
+    }
+
+    /// Constructs a [`Maze`] with default symbols from a grid of fields and a grid of their entry costs.
+    ///
+    /// Fields define walls, start and end, `costs[y][x]` is added on top of the movement cost times `10`,
+    /// the same as the digits of `set_cost_file`, though any value is accepted.
+    ///
+    /// # Errors
+    /// If the grid is empty or its rows are not the same length.
+    ///
+    /// If `costs` dimensions don't match the grid.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let fields = vec!["S....", ".W.W.", "....E"]
+    ///     .into_iter()
+    ///     .map(|row| row.chars().collect())
+    ///     .collect();
+    /// let costs = vec![vec![0, 5, 0, 5, 0], vec![0; 5], vec![5, 0, 5, 0, 0]];
+    ///
+    /// let mut maze = Maze::from_char_and_cost(fields, costs).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = vec![(0, 0), (0, 1), (1, 2), (2, 1), (3, 2), (4, 2)];
+    /// assert_eq!(maze.get_path().unwrap(), path);
+    /// ```
+    pub fn from_char_and_cost(grid: Vec<Vec<char>>, costs: Vec<Vec<usize>>) -> Result<Self> {
+        Maze::new().set_grid(grid)?.set_costs(costs)
     }
 
     /// Parses the maze into two-dimensional [`Vec`].
@@ -96,31 +422,69 @@ impl Maze {
     /// `path` - Filepath of text file that holds the data to construct the maze.
     ///
     /// # Errors
-    /// 
+    ///
     /// Returns [`Error`](crate::error::Error) if it fails to parse the text file.
-    /// 
+    ///
+    /// If rows (blank ones aside) are not the same length.
+    ///
+    /// If the separator is used as a field, that is rows shorter than the rest add up to full ones
+    /// when joined with the separator between them.
+    ///
     /// # Examples
     ///
     /// ```no_run
+    /// use astar::{Error, Maze};
+    ///
     /// fn main() -> Result<(), Error> {
     ///     // Lets say new_maze.txt contained this text:
     ///     // ".../.../..."
-    ///     let mut maze = Maze::new().set("new_maze.txt");
+    ///     let maze = Maze::new().set_inline("new_maze.txt")?;
     ///
-    ///     assert_eq!(&[vec!['.', '.', '.']; 3], maze.field())
+    ///     assert_eq!(&vec![vec!['.', '.', '.']; 3], maze.field());
+    ///     Ok(())
     /// }
     /// ```
+    ///
+    /// Separator used as a field is rejected instead of splitting the row.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_stray_separator.txt");
+    /// std::fs::write(&path, "S..\\.\\.\\..E").unwrap();
+    ///
+    /// let error = Maze::new().set_inline(path.to_str().unwrap()).err().unwrap();
+    /// assert!(error.to_string().contains("separator"));
+    ///
+    /// // Rows that are simply of different length are not.
+    /// std::fs::write(&path, "S..\\..\\..E").unwrap();
+    /// let error = Maze::new().set_inline(path.to_str().unwrap()).err().unwrap();
+    /// assert!(!error.to_string().contains("separator"));
+    ///
+    /// // Grids built without splitting are checked as well.
+    /// assert!(Maze::from_lines(&["S.\\.E"]).is_err());
+    /// ```
     pub fn set_inline(mut self, path: &str) -> Result<Self> {
-        if let Ok(maze) = fs::read_to_string(path) {
+        if let Ok(maze) = self.read_maze(path) {
             let maze = maze
                 .trim()
                 .split(self.separator)
                 .map(|slice| slice.chars().collect())
                 .collect::<Vec<Vec<char>>>();
 
-            self.maze = maze;
-            self.calculate_start();
-            self.calculate_end();
+            let width = maze.iter().map(Vec::len).max().unwrap_or(0);
+            let mut rows = maze.iter().filter(|row| !row.is_empty());
+            if rows.any(|row| row.len() != width) {
+                if is_split_row(&maze, width) {
+                    return Err(InvalidCharacters.into());
+                }
+                return Err(InvalidGrid.into());
+            }
+
+            let maze = match self.blank_line_fill {
+                Some(fill) => fill_blank_rows(maze, fill),
+                None => maze,
+            };
+            self.replace_grid(maze)?;
 
             Ok(self)
         } else {
@@ -130,17 +494,48 @@ impl Maze {
 
     /// Same as `set_inline`, if you are not using seperator to split into rows, then use set.
     ///
-    /// Set splits when it finds newline character.
+    /// Set splits when it finds newline character. Other whitespace (spaces, tabs) is kept as fields,
+    /// so spaces can be used as open fields. Empty lines are skipped.
+    /// Entry costs and elevation set for the previous grid are dropped.
+    ///
+    /// # Examples
+    /// Text parsed with `parse` is split into rows the same way.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze: Maze = "S  \nWW \nE  ".parse().unwrap();
+    /// assert_eq!(maze.dimensions(), (3, 3));
+    ///
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 5);
+    /// ```
+    ///
+    /// Replacing a grid drops its entry costs and elevation.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_replaced_costs.txt");
+    /// std::fs::write(&path, "S...\n.WW.\n...E").unwrap();
+    ///
+    /// let mut maze = Maze::from_char_and_cost(vec![vec!['S', 'E']], vec![vec![0, 0]])
+    ///     .unwrap()
+    ///     .set_elevation(vec![vec![0, 9]])
+    ///     .unwrap()
+    ///     .set(path.to_str().unwrap())
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path_cost().unwrap(), 44.0);
+    /// ```
     pub fn set(mut self, path: &str) -> Result<Self> {
-        if let Ok(maze) = fs::read_to_string(path) {
-            let maze = maze
-                .split_whitespace()
-                .map(|slice| slice.chars().collect())
-                .collect::<Vec<Vec<char>>>();
-
-            self.maze = maze;
-            self.calculate_start();
-            self.calculate_end();
+        if let Ok(maze) = self.read_maze(path) {
+            let maze = match self.blank_line_fill {
+                Some(fill) => {
+                    let rows = maze.lines().map(|line| line.chars().collect());
+                    fill_blank_rows(rows.collect(), fill)
+                }
+                None => split_rows(&maze),
+            };
+            self.replace_grid(maze)?;
 
             Ok(self)
         } else {
@@ -148,242 +543,4185 @@ impl Maze {
         }
     }
 
-    /// Sets the symbol of walls that will be inside the text file.
-    pub fn set_walls_char(mut self, symbol: char) -> Self {
-        self.wall_char = symbol;
-        self
-    }
-
-    /// Sets the symbol of start field that will be inside the text file.
-    pub fn set_start_char(mut self, symbol: char) -> Self {
-        self.start_char = symbol;
-        self.calculate_start();
-        self
-    }
-
-    /// Sets the symbol of end field that will be inside the text file.
-    pub fn set_end_char(mut self, symbol: char) -> Self {
-        self.end_char = symbol;
-        self.calculate_end();
-        self
-    }
-
-    /// Sets the symbol for path.
-    pub fn set_path_char(mut self, symbol: char) -> Self {
-        self.path_char = symbol;
-        self
-    }
-
-    /// Sets the symbol that marks start of new row inside the text file.
-    pub fn set_separator(mut self, symbol: char) -> Self {
-        self.separator = symbol;
-        self
-    }
-
-    /// Returns current path character.
-    pub fn path_char(&self) -> char {
-        self.path_char
-    }
-
-    /// Returns [`char`] that represents the wall inside the text.
-    pub fn wall(&self) -> char {
-        self.wall_char
-    }
-
-    /// Returns reference to formatted maze.
-    pub fn field(&self) -> &[Vec<char>] {
-        &self.maze
-    }
-
-    /// Returns current end [`char`].
-    pub fn end_char(&self) -> char {
-        self.end_char
-    }
+    /// Parses the maze choosing between `set` and `set_inline` automatically.
+    ///
+    /// Rows are split on newlines first, if that yields a single row or rows of different length,
+    /// they are split on both newlines and the separator, so files mixing the two are accepted as well.
+    ///
+    /// # Errors
+    /// If the file can't be read.
+    ///
+    /// If neither way of splitting produces rows of the same length.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_auto.txt");
+    /// let path = path.to_str().unwrap();
+    ///
+    /// for text in ["S..\\WW.\\..E", "S..\\WW.\n..E", "S..\nWW.\n..E"] {
+    ///     std::fs::write(path, text).unwrap();
+    ///     let mut maze = Maze::new().set_auto(path).unwrap();
+    ///
+    ///     assert_eq!(maze.dimensions(), (3, 3));
+    ///     maze.try_solve().unwrap();
+    ///     assert_eq!(maze.get_path().unwrap().len(), 4);
+    /// }
+    /// ```
+    pub fn set_auto(self, path: &str) -> Result<Self> {
+        if let Ok(maze) = self.read_maze(path) {
+            let rows = |pattern: &dyn Fn(char) -> bool| {
+                maze.split(pattern)
+                    .filter(|slice| !slice.is_empty())
+                    .map(|slice| slice.chars().collect())
+                    .collect::<Vec<Vec<char>>>()
+            };
 
-    /// Returns current start [`char`].
-    pub fn start_char(&self) -> char {
-        self.start_char
-    }
+            let newline = |char| char == '\n' || char == '\r';
+            let lines = rows(&newline);
+            if lines.len() > 1 && lines.iter().all(|row| row.len() == lines[0].len()) {
+                return self.set_grid(lines);
+            }
 
-    /// Returns current separator [`char`].
-    pub fn separator_char(&self) -> char {
-        self.separator
+            let separator = self.separator;
+            let mixed = rows(&|char| newline(char) || char == separator);
+            self.set_grid(mixed)
+        } else {
+            Err(InvalidFilePath.into())
+        }
     }
 
-    /// Returns maze length by number of collumns.
-    pub fn x_len(&self) -> usize {
-        self.maze[0].len()
-    }
+    /// Loads per-cell entry costs from a text file aligned to the maze grid.
+    ///
+    /// Each field must be a single digit `0-9`, rows are split the same way as in `set`.
+    /// Entering a field adds `digit * 10` on top of the usual movement cost (`10` straight, `14` diagonal),
+    /// so the heuristic stays admissible and `0` behaves like an unweighted field.
+    ///
+    /// Maze must be set before calling this method.
+    ///
+    /// # Errors
+    /// If the file can't be read.
+    ///
+    /// If the file contains non-digit fields or its dimensions don't match the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze_path = std::env::temp_dir().join("astar_weighted_maze.txt");
+    /// let cost_path = std::env::temp_dir().join("astar_weighted_costs.txt");
+    /// std::fs::write(&maze_path, "S....\n.....\n....E").unwrap();
+    /// std::fs::write(&cost_path, "00000\n09990\n00000").unwrap();
+    ///
+    /// let mut maze = Maze::new()
+    ///     .set(maze_path.to_str().unwrap())
+    ///     .unwrap()
+    ///     .set_cost_file(cost_path.to_str().unwrap())
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Path goes around the expensive middle of the maze.
+    /// let path = maze.get_path().unwrap();
+    /// assert!([(1, 1), (2, 1), (3, 1)].iter().all(|field| !path.contains(field)));
+    /// ```
+    pub fn set_cost_file(self, path: &str) -> Result<Self> {
+        if let Ok(costs) = fs::read_to_string(path) {
+            let costs = costs
+                .split_whitespace()
+                .map(|slice| {
+                    slice
+                        .chars()
+                        .map(|char| char.to_digit(10).map(|digit| digit as usize))
+                        .collect::<Option<Vec<usize>>>()
+                })
+                .collect::<Option<Vec<Vec<usize>>>>()
+                .ok_or(Error::from(InvalidCostFile))?;
 
-    /// Returns maze length by number of rows.
-    pub fn y_len(&self) -> usize {
-        self.maze.len()
+            self.set_costs(costs)
+        } else {
+            Err(InvalidFilePath.into())
+        }
     }
 
-    /// Returns maze dimensions `(x_len, y_len)`.
-    pub fn dimensions(&self) -> (usize, usize) {
-        (self.x_len(), self.y_len())
+    /// Constructs a [`Maze`] from a text file where every field is a digit `0-9` holding its entry cost,
+    /// `9` fields are walls. Same as `Maze::new().set_digit_grid(path, 9)`.
+    ///
+    /// # Errors
+    /// Same as `set_digit_grid`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_digit_grid.txt");
+    /// std::fs::write(&path, "S5559\n00000\n9999E\n").unwrap();
+    ///
+    /// let mut maze = Maze::from_digit_grid(path.to_str().unwrap()).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // The path follows the cheap channel of zeros.
+    /// assert_eq!(maze.wall(), '9');
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1), (2, 1), (3, 1), (4, 2)]);
+    /// ```
+    pub fn from_digit_grid(path: &str) -> Result<Self> {
+        Maze::new().set_digit_grid(path, 9)
     }
 
-    /// Sets the path in our maze (tries to solve the maze).
+    /// Parses the maze from a text file where every field is a digit `0-9`, rows are split the same way as in `set`.
+    ///
+    /// Each digit is the entry cost of its field, the same as in `set_cost_file`, except `wall_digit`
+    /// which becomes the wall symbol. Start and end are marked with their usual symbols and cost nothing to enter.
     ///
     /// # Errors
-    /// If symbols for `start`/`end` are not found inside the text file.
-    /// 
-    /// If it is impossible to solve the maze.
+    /// If the file can't be read.
     ///
-    /// If `start`, `end`, `separator` or `wall` share the same character, it will also return [`Error`].
-    pub fn try_solve(&mut self) -> Result<()> {
-        if let (Some(start), Some(end)) = (self.start, self.end) {
+    /// If `wall_digit` is not a digit, the file contains other fields than digits, start and end,
+    /// or its rows are not the same length.
+    pub fn set_digit_grid(mut self, path: &str, wall_digit: u32) -> Result<Self> {
+        if let Ok(maze) = self.read_maze(path) {
+            let grid = split_rows(&maze);
+
+            let wall = char::from_digit(wall_digit, 10).ok_or(Error::from(InvalidCostFile))?;
+            self.wall_char = wall;
             if self.are_chars_invalid() {
                 return Err(InvalidCharacters.into());
             }
 
-            let start_node = Node {
-                position: start,
-                g_cost: 0,
-                h_cost: Node::heuristic(start, end),
-                previous: None,
-            };
-            let priority = Priority(start_node.f_cost());
-
-            let mut open: PriorityQueue<Node, Priority> =
-                PriorityQueue::from(vec![(start_node, priority)]);
-            let mut closed: HashSet<Position> = HashSet::new();
-
-            while !open.is_empty() {
-                let current = open.pop().unwrap();
-
-                if current.0.position.xy() == end.xy() {
-                    let mut path = Path {
-                        fields: VecDeque::from(vec![current.0.position.xy_usize()]),
-                    };
-                    let mut curr = current.0.previous;
-
-                    while let Some(node) = curr.take() {
-                        path.fields.push_front(node.position.xy_usize());
-                        curr = node.previous;
-                    }
+            let costs = grid
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|char| match char.to_digit(10) {
+                            Some(digit) => Some(digit as usize),
+                            None if *char == self.start_char || *char == self.end_char => Some(0),
+                            None => None,
+                        })
+                        .collect::<Option<Vec<usize>>>()
+                })
+                .collect::<Option<Vec<Vec<usize>>>>()
+                .ok_or(Error::from(InvalidCostFile))?;
 
-                    self.path = Some(path);
-                    return Ok(());
-                }
-                for mut neighbour in current.0.neighbours(self) {
-                    let f_cost = neighbour.f_cost();
-
-                    if closed.get(&neighbour.position).is_some() {
-                        continue;
-                    } else if let Some(node) = open.get(&neighbour) {
-                        if node.0.lower_cost(&neighbour) {
-                            continue;
-                        } else {
-                            neighbour.previous = Some(Box::new(current.0.clone()));
-                            open.push(neighbour, Priority(f_cost));
-                        }
-                    } else {
-                        neighbour.previous = Some(Box::new(current.0.clone()));
-                        open.push(neighbour, Priority(f_cost));
-                    }
-                }
-                closed.insert(current.0.position);
-            }
-            Err(MazeIsNotSolvable.into())
+            self.set_grid(grid)?.set_costs(costs)
         } else {
-            Err(StartEndNotSet.into())
+            Err(InvalidFilePath.into())
         }
     }
 
-    /// Returns [`Vec`] that represents the shortest path from `Start` to the `End`
+    /// Sets elevation of every field, `elevation[y][x]` must match the maze dimensions.
     ///
-    /// # Errors 
-    /// If [`Maze`] is not solved.
-    pub fn get_path(&self) -> Result<Vec<(usize, usize)>> {
+    /// Moving uphill costs extra `10` for each unit of elevation gained, moving downhill or on flat ground costs nothing
+    /// extra. This makes cost of moving between two fields depend on the direction.
+    ///
+    /// # Errors
+    /// If `elevation` dimensions don't match the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.E", "..."])
+    ///     .unwrap()
+    ///     .set_elevation(vec![vec![0, 9, 0], vec![0, 0, 0]])
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Climbing over the hill costs more than walking around it.
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1), (2, 0)]);
+    /// ```
+    pub fn set_elevation(mut self, elevation: Vec<Vec<i32>>) -> Result<Self> {
+        if elevation.len() != self.y_len()
+            || elevation
+                .iter()
+                .zip(&self.maze)
+                .any(|(a, b)| a.len() != b.len())
+        {
+            return Err(InvalidElevation.into());
+        }
+
+        self.elevation = Some(elevation);
+        self.invalidate_paths();
+
+        Ok(self)
+    }
+
+    /// Removes entry costs set by `set_cost_file` and elevation set by `set_elevation`.
+    ///
+    /// Every step costs only its base `10` or `14` afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let lines = ["S...", ".WW.", "...E"];
+    /// let mut plain = Maze::from_lines(&lines).unwrap();
+    /// let mut cleared = Maze::from_lines(&lines)
+    ///     .unwrap()
+    ///     .set_elevation(vec![vec![0, 9, 9, 0], vec![0, 0, 0, 0], vec![0, 0, 0, 0]])
+    ///     .unwrap()
+    ///     .clear_terrain();
+    ///
+    /// // Solve after clearing is the same as one that never had terrain.
+    /// plain.try_solve().unwrap();
+    /// cleared.try_solve().unwrap();
+    /// assert_eq!(cleared.get_path().unwrap(), plain.get_path().unwrap());
+    /// assert_eq!(cleared.cost_between((0, 0), (3, 2)).unwrap(), 44);
+    /// ```
+    pub fn clear_terrain(mut self) -> Self {
+        self.costs = None;
+        self.elevation = None;
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets whether terrain of the start and end fields is ignored, disabled by default.
+    ///
+    /// If enabled, entering either of them costs only the base `10` or `14`, their entry costs are not added
+    /// and moving onto or off them never counts as climbing.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let fields = vec![vec!['S', '.', 'E']];
+    /// let costs = vec![vec![0, 0, 9]];
+    ///
+    /// let mut maze = Maze::from_char_and_cost(fields.clone(), costs.clone()).unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path_cost().unwrap(), 110.0);
+    ///
+    /// let mut maze = Maze::from_char_and_cost(fields, costs)
+    ///     .unwrap()
+    ///     .set_endpoints_plain(true);
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path_cost().unwrap(), 20.0);
+    /// ```
+    pub fn set_endpoints_plain(mut self, plain: bool) -> Self {
+        self.endpoints_plain = plain;
+        self.invalidate_paths();
+        self
+    }
+
+    /// Limits how many times the field at `position` can be entered along the path.
+    ///
+    /// Capacity of `0` makes the field impassable. Search keeps track of visits to limited
+    /// fields, so paths that need to pass a field more than once respect the limit as well.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Figure eight, both loops meet in a single field.
+    /// let text = "...W...\nS.W.W.E\n...W...";
+    /// let mut maze = Maze::try_from(text).unwrap().set_cell_capacity((3, 1), 1);
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.get_path().unwrap().contains(&(3, 1)));
+    ///
+    /// let mut maze = Maze::try_from(text).unwrap().set_cell_capacity((3, 1), 0);
+    /// assert!(maze.try_solve().is_err());
+    /// ```
+    pub fn set_cell_capacity(mut self, position: (usize, usize), capacity: usize) -> Self {
+        let position = Position(position);
+
+        match self.capacity_index(position) {
+            Some(index) => self.capacities[index].1 = capacity,
+            None => self.capacities.push((position, capacity)),
+        }
+        self.invalidate_paths();
+        self.update_connectivity();
+        self
+    }
+
+    /// Links fields `a` and `b` (`(x, y)`) with a portal, moving between them in either direction costs `cost`.
+    ///
+    /// Portal cost replaces the usual step cost, entry cost of the field is still added. Linking the same
+    /// pair again changes the cost. Heuristic is lowered near portals, so the found path stays the shortest.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.W...", "..W..E"])
+    ///     .unwrap()
+    ///     .set_portal((1, 0), (3, 0), 5);
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (3, 0), (4, 1), (5, 1)]);
+    /// ```
+    pub fn set_portal(mut self, a: (usize, usize), b: (usize, usize), cost: usize) -> Self {
+        let (a, b) = (Position(a), Position(b));
+
+        match self.portal_index(a, b) {
+            Some(index) => self.portals[index].2 = cost,
+            None => self.portals.push((a, b, cost)),
+        }
+        self.invalidate_paths();
+        self.update_connectivity();
+        self
+    }
+
+    /// Sets whether ANSI escape sequences (e.g. colour codes) are removed from the text file before parsing.
+    ///
+    /// Disabled by default, has to be set before `set`/`set_inline` is called.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_coloured.txt");
+    /// std::fs::write(&path, "\x1B[32mS\x1B[0m..\nWW.\n..\x1B[1;31mE\x1B[0m").unwrap();
+    ///
+    /// let mut maze = Maze::new()
+    ///     .set_strip_ansi(true)
+    ///     .set(path.to_str().unwrap())
+    ///     .unwrap();
+    /// assert_eq!(maze.field()[0], vec!['S', '.', '.']);
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 4);
+    /// ```
+    pub fn set_strip_ansi(mut self, strip: bool) -> Self {
+        self.strip_ansi = strip;
+        self
+    }
+
+    /// Sets the symbol that fills blank lines inside the text file, by default they are skipped by `set`
+    /// and parsed as empty rows by `set_inline` (with `'\n'` as the separator).
+    ///
+    /// Each blank line becomes a row of `fill` as wide as the widest row, blank lines before the first
+    /// and after the last row are still dropped. Has to be set before `set`/`set_inline` is called.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_blank_row.txt");
+    /// std::fs::write(&path, "S..\n\n..E\n").unwrap();
+    ///
+    /// let maze = Maze::new()
+    ///     .set_separator('\n')
+    ///     .set_blank_line_fill('W')
+    ///     .set_inline(path.to_str().unwrap())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(maze.dimensions(), (3, 3));
+    /// assert_eq!(maze.field()[1], vec!['W'; 3]);
+    /// assert!(!maze.is_solvable());
+    /// ```
+    pub fn set_blank_line_fill(mut self, fill: char) -> Self {
+        self.blank_line_fill = Some(fill);
+        self
+    }
+
+    /// Sets whether `set_start` and `set_end` move an endpoint placed on a field that is not walkable
+    /// to the nearest walkable one (see `snap_to_open`) instead of returning an error. Disabled by default.
+    pub fn set_snap_endpoints(mut self, snap: bool) -> Self {
+        self.snap_endpoints = snap;
+        self
+    }
+
+    /// Sets whether the outermost ring of fields is treated as a wall.
+    ///
+    /// `start` and `end` stay walkable even if they lie on the border.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.....E", ".......", "......."])
+    ///     .unwrap()
+    ///     .set_solid_border(true);
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Path leaves the top row right after start and returns to it only at the end.
+    /// let path = maze.get_path().unwrap();
+    /// assert_eq!(path[1..path.len() - 1], [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+    /// assert_eq!(maze.cost_between((0, 0), (6, 0)).unwrap(), 68);
+    /// ```
+    pub fn set_solid_border(mut self, solid: bool) -> Self {
+        self.solid_border = solid;
+        self.invalidate_paths();
+        self.update_connectivity();
+        self
+    }
+
+    /// Sets the symbol that marks comment lines inside the text file.
+    ///
+    /// Lines starting with `prefix` are skipped by `set`, `set_inline` and `set_auto`,
+    /// has to be set before the maze is parsed.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_commented.txt");
+    /// std::fs::write(&path, "# level 1\n# 3 x 3\nS..\n  # walls\nWW.\n..E").unwrap();
+    ///
+    /// let mut maze = Maze::new()
+    ///     .set_comment_prefix('#')
+    ///     .set(path.to_str().unwrap())
+    ///     .unwrap();
+    /// assert_eq!(maze.dimensions(), (3, 3));
+    /// maze.try_solve().unwrap();
+    /// ```
+    pub fn set_comment_prefix(mut self, prefix: char) -> Self {
+        self.comment_prefix = Some(prefix);
+        self
+    }
+
+    /// Sets the symbol of walls that will be inside the text file.
+    pub fn set_walls_char(mut self, symbol: char) -> Self {
+        self.wall_char = symbol;
+        self.update_connectivity();
+        self
+    }
+
+    /// Sets the symbol of start field that will be inside the text file.
+    pub fn set_start_char(mut self, symbol: char) -> Self {
+        self.start_char = symbol;
+        self.calculate_start();
+        self
+    }
+
+    /// Sets the symbol of end field that will be inside the text file.
+    pub fn set_end_char(mut self, symbol: char) -> Self {
+        self.end_char = symbol;
+        self.calculate_end();
+        self
+    }
+
+    /// Sets the symbol for path.
+    pub fn set_path_char(mut self, symbol: char) -> Self {
+        self.path_char = symbol;
+        self
+    }
+
+    /// Sets the symbol that marks start of new row inside the text file.
+    pub fn set_separator(mut self, symbol: char) -> Self {
+        self.separator = symbol;
+        self
+    }
+
+    /// Returns current path character.
+    pub fn path_char(&self) -> char {
+        self.path_char
+    }
+
+    /// Returns [`char`] that represents the wall inside the text.
+    pub fn wall(&self) -> char {
+        self.wall_char
+    }
+
+    /// Returns reference to formatted maze.
+    pub fn field(&self) -> &[Vec<char>] {
+        &self.maze
+    }
+
+    /// Returns current end [`char`].
+    pub fn end_char(&self) -> char {
+        self.end_char
+    }
+
+    /// Returns current start [`char`].
+    pub fn start_char(&self) -> char {
+        self.start_char
+    }
+
+    /// Returns current separator [`char`].
+    pub fn separator_char(&self) -> char {
+        self.separator
+    }
+
+    /// Returns positions of every start symbol inside the maze, row by row.
+    ///
+    /// Only the first one is used as `start`, the rest are usually an accidental duplicate.
+    pub fn found_starts(&self) -> Vec<(usize, usize)> {
+        self.find_all(self.start_char)
+    }
+
+    /// Returns positions of every end symbol inside the maze, row by row.
+    ///
+    /// Only the first one is used as `end`, the rest are usually an accidental duplicate.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..E", "....", "E..."]).unwrap();
+    /// assert_eq!(maze.found_starts(), vec![(0, 0)]);
+    /// assert_eq!(maze.found_ends(), vec![(3, 0), (0, 2)]);
+    ///
+    /// // First end row by row is the one solved for.
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().last(), Some(&(3, 0)));
+    /// ```
+    pub fn found_ends(&self) -> Vec<(usize, usize)> {
+        self.find_all(self.end_char)
+    }
+
+    /// Returns `true` if the maze has at least one field and all of its rows are the same length.
+    ///
+    /// Mazes parsed with `set` are not checked, ragged rows can't be solved reliably.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_ragged_maze.txt");
+    /// std::fs::write(&path, "S...\n..\n...E\n").unwrap();
+    ///
+    /// let maze = Maze::new().set(path.to_str().unwrap()).unwrap();
+    /// assert!(!maze.is_rectangular());
+    /// assert_eq!(maze.max_row_width(), 4);
+    /// ```
+    pub fn is_rectangular(&self) -> bool {
+        !self.maze.is_empty()
+            && !self.maze[0].is_empty()
+            && self.maze.iter().all(|row| row.len() == self.maze[0].len())
+    }
+
+    /// Returns length of the longest row, `0` if the maze is empty.
+    pub fn max_row_width(&self) -> usize {
+        self.maze.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Returns maze length by number of collumns.
+    pub fn x_len(&self) -> usize {
+        self.maze[0].len()
+    }
+
+    /// Returns maze length by number of rows.
+    pub fn y_len(&self) -> usize {
+        self.maze.len()
+    }
+
+    /// Returns maze dimensions `(x_len, y_len)`.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.x_len(), self.y_len())
+    }
+
+    /// Returns a hash of the fields and symbols (start, end, wall, path and separator) of the maze.
+    ///
+    /// The hash is FNV-1a, so it is the same across runs and platforms and can key caches kept outside of the program.
+    /// Editing a field changes it, reverting the edit restores it. Costs, elevation and other settings are not included.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "...", "..E"]).unwrap();
+    /// let original = maze.grid_hash();
+    ///
+    /// maze.set_cell((1, 1), 'W').unwrap();
+    /// assert_ne!(maze.grid_hash(), original);
+    ///
+    /// maze.set_cell((1, 1), '.').unwrap();
+    /// assert_eq!(maze.grid_hash(), original);
+    /// ```
+    pub fn grid_hash(&self) -> u64 {
+        let symbols = [
+            self.start_char,
+            self.end_char,
+            self.wall_char,
+            self.path_char,
+            self.separator,
+        ];
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |value: u64| {
+            for byte in value.to_le_bytes() {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+
+        for symbol in symbols {
+            write(u64::from(symbol));
+        }
+        // Row lengths are hashed as well, so the same fields split into different rows hash differently.
+        for row in &self.maze {
+            write(row.len() as u64);
+            for field in row {
+                write(u64::from(*field));
+            }
+        }
+        hash
+    }
+
+    /// Returns every field (`(x, y)`) whose symbol differs between `a` and `b`, with its symbol in `a` and in `b`.
+    ///
+    /// Fields are listed row by row. Only symbols are compared, other settings of the mazes are ignored.
+    ///
+    /// # Errors
+    /// If the mazes don't have the same number of rows, or rows of the same length.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let before = Maze::from_lines(&["S..", ".W.", "..E"]).unwrap();
+    /// let after = Maze::from_lines(&["S.W", "...", "..E"]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Maze::grid_diff(&before, &after).unwrap(),
+    ///     vec![((2, 0), '.', 'W'), ((1, 1), 'W', '.')]
+    /// );
+    /// ```
+    pub fn grid_diff(a: &Maze, b: &Maze) -> Result<Vec<(Field, char, char)>> {
+        let same_dimensions = a.maze.len() == b.maze.len()
+            && a.maze.iter().zip(&b.maze).all(|(a, b)| a.len() == b.len());
+        if !same_dimensions {
+            return Err(DimensionMismatch.into());
+        }
+
+        Ok(a.maze
+            .iter()
+            .zip(&b.maze)
+            .enumerate()
+            .flat_map(|(y, (a, b))| {
+                a.iter()
+                    .zip(b)
+                    .enumerate()
+                    .filter(|(_, (a, b))| a != b)
+                    .map(move |(x, (&a, &b))| ((x, y), a, b))
+            })
+            .collect())
+    }
+
+    /// Replaces the symbol of the field at `position` (`(x, y)`), invalidating the current path.
+    ///
+    /// Start/end positions are recalculated if a marker was placed or removed.
+    ///
+    /// # Errors
+    /// If `position` is outside of the maze.
+    pub fn set_cell(&mut self, position: (usize, usize), symbol: char) -> Result<()> {
+        let position = Position(position);
+        if !self.in_bounds(position) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        let (x, y) = position.xy_usize();
+        let was_walkable = self.is_walkable(position);
+        let previous = std::mem::replace(&mut self.maze[y][x], symbol);
+
+        let markers = [self.start_char, self.end_char];
+        let moved_endpoint = markers.contains(&previous) || markers.contains(&symbol);
+        if moved_endpoint {
+            self.calculate_start();
+            self.calculate_end();
+        }
+        self.invalidate_paths();
+
+        match (was_walkable, self.is_walkable(position)) {
+            // Endpoints are exempt from the solid border, moving them can close border fields.
+            _ if moved_endpoint && self.solid_border => self.update_connectivity(),
+            // Walls close the fields around them once inflated.
+            _ if self.wall_inflation > 0 => self.update_connectivity(),
+            (true, false) => self.update_connectivity(),
+            (false, true) => {
+                if let Some(mut connectivity) = self.connectivity.take() {
+                    connectivity.open(self, position);
+                    self.connectivity = Some(connectivity);
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Moves the start to `position` (`(x, y)`), the field of the previous start becomes an open `'.'` field.
+    ///
+    /// If `set_snap_endpoints` is enabled and `position` is not walkable, the start is placed on the nearest
+    /// walkable field instead, see `snap_to_open`.
+    ///
+    /// # Errors
+    /// If `position` is outside of the maze.
+    ///
+    /// If the field at `position` is not walkable (and there is no walkable field to snap to).
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.W", "..W", "WWE"]).unwrap();
+    /// assert!(maze.set_start((2, 0)).is_err());
+    ///
+    /// let mut maze = maze.set_snap_endpoints(true);
+    /// maze.set_start((2, 0)).unwrap();
+    /// assert_eq!(maze.found_starts(), vec![(1, 0)]);
+    /// ```
+    pub fn set_start(&mut self, position: (usize, usize)) -> Result<()> {
+        let position = self.endpoint_field(position)?;
+        if let Some(start) = self.start {
+            self.set_cell(start.xy_usize(), '.')?;
+        }
+        self.set_cell(position, self.start_char)
+    }
+
+    /// Moves the end to `position` (`(x, y)`), the field of the previous end becomes an open `'.'` field.
+    ///
+    /// Snapping works the same as in `set_start`.
+    ///
+    /// # Errors
+    /// Same as `set_start`.
+    pub fn set_end(&mut self, position: (usize, usize)) -> Result<()> {
+        let position = self.endpoint_field(position)?;
+        if let Some(end) = self.end {
+            self.set_cell(end.xy_usize(), '.')?;
+        }
+        self.set_cell(position, self.end_char)
+    }
+
+    /// Returns the walkable field nearest to `position` (`(x, y)`), `position` itself if it is walkable.
+    ///
+    /// Fields are searched breadth first in all 8 directions, walls included, so the distance is the number
+    /// of king moves. Straight neighbours are visited before diagonal ones.
+    /// Returns [`None`] if `position` is outside of the maze or no field is walkable.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S.WWW", "WWWWW", "WWW.E"]).unwrap();
+    /// assert_eq!(maze.snap_to_open((0, 0)), Some((0, 0)));
+    /// assert_eq!(maze.snap_to_open((2, 0)), Some((1, 0)));
+    /// assert_eq!(maze.snap_to_open((3, 1)), Some((3, 2)));
+    /// ```
+    pub fn snap_to_open(&self, position: (usize, usize)) -> Option<(usize, usize)> {
+        let position = Position(position);
+        if !self.in_bounds(position) {
+            return None;
+        }
+
+        let (straight, diagonal): (Vec<_>, Vec<_>) = Direction::ALL
+            .into_iter()
+            .partition(|direction| !direction.is_diagonal());
+
+        let mut visited = HashSet::from([position]);
+        let mut queue = VecDeque::from([position]);
+        while let Some(position) = queue.pop_front() {
+            if self.is_walkable(position) {
+                return Some(position.xy_usize());
+            }
+            let (x, y) = position.xy();
+            for direction in straight.iter().chain(&diagonal) {
+                let (offset_x, offset_y) = direction.offset();
+                let (x, y) = (x + offset_x, y + offset_y);
+                if x < 0 || y < 0 {
+                    continue;
+                }
+
+                let neighbour = Position((x as usize, y as usize));
+                if self.in_bounds(neighbour) && visited.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes outermost rows and columns made only of walls, at least one row and column is always kept.
+    ///
+    /// Everything that refers to fields is shifted to match the trimmed maze:
+    /// - start, end and path (walls are never part of the path, so it stays valid),
+    /// - entry costs, elevation and capacities,
+    /// - portals, walls between fields and turn restrictions,
+    /// - avoided fields.
+    ///
+    /// Paths cached by `solve_between` are cleared.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze};
+    ///
+    /// let mut maze = Maze::from_lines(&["WWWWW", "WS.EW", "WWWWW", "WWWWW"]).unwrap();
+    /// maze.trim_borders();
+    ///
+    /// assert_eq!(maze.dimensions(), (3, 1));
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (2, 0)]);
+    ///
+    /// // Portals are shifted as well.
+    /// let mut maze = Maze::from_lines(&["WWWWWWW", "WS.W.EW", "WWWWWWW"])
+    ///     .unwrap()
+    ///     .set_portal((2, 1), (4, 1), 5);
+    /// maze.trim_borders();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (3, 0), (4, 0)]);
+    ///
+    /// // So are walls between fields.
+    /// let mut maze = Maze::from_lines(&["WWWW", "WS.W", "W.EW", "WWWW"]).unwrap();
+    /// maze.set_wall_between((1, 1), (2, 2)).unwrap();
+    /// maze.trim_borders();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 3);
+    ///
+    /// // And turn restrictions.
+    /// let lines = ["WWWWWWWWW", "W.......W", "W.WWWWW.W", "W......EW", "W.WWWWWWW", "WSWWWWWWW"];
+    /// let mut maze = Maze::from_lines(&lines).unwrap();
+    /// maze.set_turn_restriction((1, 4), Direction::North, Direction::NorthEast).unwrap();
+    /// maze.set_turn_restriction((1, 3), Direction::North, Direction::East).unwrap();
+    /// maze.set_turn_restriction((1, 3), Direction::North, Direction::South).unwrap();
+    /// maze.trim_borders();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap()[..5], [(0, 4), (0, 3), (0, 2), (0, 1), (1, 2)]);
+    /// ```
+    pub fn trim_borders(&mut self) {
+        let wall = self.wall_char;
+        let width = self.max_row_width();
+        let is_wall_row = |row: &Vec<char>| row.iter().all(|char| *char == wall);
+
+        let (mut top, mut bottom) = (0, self.maze.len());
+        while bottom - top > 1 && is_wall_row(&self.maze[top]) {
+            top += 1;
+        }
+        while bottom - top > 1 && is_wall_row(&self.maze[bottom - 1]) {
+            bottom -= 1;
+        }
+
+        let is_wall_column = |x: usize| {
+            self.maze[top..bottom]
+                .iter()
+                .all(|row| row.get(x).is_none_or(|char| *char == wall))
+        };
+        let (mut left, mut right) = (0, width);
+        while right - left > 1 && is_wall_column(left) {
+            left += 1;
+        }
+        while right - left > 1 && is_wall_column(right - 1) {
+            right -= 1;
+        }
+
+        if (top, bottom, left, right) == (0, self.maze.len(), 0, width) {
+            return;
+        }
+
+        let inside = |(x, y): Field| (left..right).contains(&x) && (top..bottom).contains(&y);
+        let shift = |(x, y): Field| (x - left, y - top);
+
+        self.maze = crop(&self.maze, top..bottom, left..right);
+        self.costs = self
+            .costs
+            .take()
+            .map(|costs| crop(&costs, top..bottom, left..right));
+        self.elevation = self
+            .elevation
+            .take()
+            .map(|elevation| crop(&elevation, top..bottom, left..right));
+        self.capacities = self
+            .capacities
+            .drain(..)
+            .filter(|(position, _)| inside(position.0))
+            .map(|(position, capacity)| (Position(shift(position.0)), capacity))
+            .collect();
+        self.avoid = self
+            .avoid
+            .drain()
+            .filter(|position| inside(position.0))
+            .map(|position| Position(shift(position.0)))
+            .collect();
+        self.portals = self
+            .portals
+            .drain(..)
+            .filter(|(a, b, _)| inside(a.0) && inside(b.0))
+            .map(|(a, b, cost)| (Position(shift(a.0)), Position(shift(b.0)), cost))
+            .collect();
+        self.turn_restrictions = self
+            .turn_restrictions
+            .drain()
+            .filter(|(at, _, _)| inside(at.0))
+            .map(|(at, from, to)| (Position(shift(at.0)), from, to))
+            .collect();
+        self.thin_walls = self
+            .thin_walls
+            .drain()
+            .filter(|&(a, b)| inside(a) && inside(b))
+            .map(|(a, b)| {
+                let (a, b) = (shift(a), shift(b));
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        if let Some(path) = &mut self.path {
+            for field in path.fields.iter_mut() {
+                *field = shift(*field);
+            }
+        }
+
+        self.clear_path_cache();
+        self.calculate_start();
+        self.calculate_end();
+        self.update_connectivity();
+    }
+
+    /// Makes `cells` (`(x, y)`) impassable for the next `try_solve` (or one of its variants), without editing the maze.
+    ///
+    /// Fields are avoided by every search until the maze is solved, then the set is cleared.
+    /// Setting an empty set clears it right away. Connectivity used by `is_solvable` ignores avoided fields.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.E", "..."]).unwrap();
+    ///
+    /// maze.set_avoid(HashSet::from([(1, 0)]));
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1), (2, 0)]);
+    ///
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (2, 0)]);
+    ///
+    /// // Editing the maze while fields are avoided doesn't split it for good.
+    /// let mut maze = Maze::from_lines(&["S....E"]).unwrap();
+    /// maze.set_avoid(HashSet::from([(2, 0), (3, 0)]));
+    /// maze.set_cell((1, 0), 'W').unwrap();
+    /// maze.set_cell((1, 0), '.').unwrap();
+    /// assert!(maze.is_solvable());
+    ///
+    /// assert!(maze.try_solve().is_err());
+    /// assert!(maze.is_solvable());
+    /// assert!(maze.try_solve().is_ok());
+    /// ```
+    pub fn set_avoid(&mut self, cells: HashSet<(usize, usize)>) {
+        self.avoid = cells.into_iter().map(Position).collect();
+        self.invalidate_paths();
+    }
+
+    /// Places a thin wall between fields `a` and `b` (`(x, y)`), so neither can be entered from the other,
+    /// while both stay open otherwise. Invalidates the current path.
+    ///
+    /// # Errors
+    /// If either position is outside of the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["SE", "W."]).unwrap();
+    /// maze.set_wall_between((0, 0), (1, 0)).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1), (1, 0)]);
+    /// ```
+    pub fn set_wall_between(&mut self, a: (usize, usize), b: (usize, usize)) -> Result<()> {
+        if !self.in_bounds(Position(a)) || !self.in_bounds(Position(b)) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        self.thin_walls.insert((a.min(b), a.max(b)));
+        self.invalidate_paths();
+        self.update_connectivity();
+        Ok(())
+    }
+
+    /// Makes every field within `radius` fields of a wall (Chebyshev distance) impassable, so the path keeps
+    /// clearance around walls. Radius of `0` removes the inflation. Invalidates the current path.
+    ///
+    /// Symbols of the maze are left untouched and walls added later are inflated as well. `start` and `end` stay
+    /// walkable even if they lie next to a wall, bounds of the maze are not inflated.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&[
+    ///     "S....W....E",
+    ///     ".....W.....",
+    ///     "...........",
+    ///     ".....W.....",
+    ///     ".....W.....",
+    ///     "...........",
+    ///     "...........",
+    ///     "...........",
+    ///     ".....W.....",
+    /// ])
+    /// .unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.get_path().unwrap().contains(&(5, 2)));
+    ///
+    /// // Narrow gap is closed, path has to go through the middle of the wide one.
+    /// maze.inflate_walls(1);
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.get_path().unwrap().contains(&(5, 6)));
+    /// assert_eq!(maze.field()[2][5], '.');
+    /// ```
+    pub fn inflate_walls(&mut self, radius: usize) {
+        self.wall_inflation = radius;
+        self.invalidate_paths();
+        self.update_connectivity();
+    }
+
+    /// Forbids turning at field `at` (`(x, y)`) towards `to` after arriving there heading `from`,
+    /// e.g. `(Direction::North, Direction::West)` forbids turning left when driving north. Invalidates the current path.
+    ///
+    /// Once any turn is restricted the search keeps track of the heading, so a field can be passed
+    /// more than once in different directions. Start has no heading, turns there are never restricted.
+    /// Restrictions are ignored by `set_resolution`, reachability queries and `set_path`.
+    ///
+    /// # Errors
+    /// If `at` is outside of the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze};
+    ///
+    /// let lines = [".......", ".WWWWW.", "......E", ".WWWWWW", "SWWWWWW"];
+    /// let mut maze = Maze::from_lines(&lines).unwrap();
+    ///
+    /// // No right turns (or U-turn) off the northbound road, path has to detour further north.
+    /// maze.set_turn_restriction((0, 3), Direction::North, Direction::NorthEast).unwrap();
+    /// maze.set_turn_restriction((0, 2), Direction::North, Direction::East).unwrap();
+    /// maze.set_turn_restriction((0, 2), Direction::North, Direction::South).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap()[..5], [(0, 4), (0, 3), (0, 2), (0, 1), (1, 2)]);
+    /// ```
+    pub fn set_turn_restriction(
+        &mut self,
+        at: (usize, usize),
+        from: Direction,
+        to: Direction,
+    ) -> Result<()> {
+        let at = Position(at);
+        if !self.in_bounds(at) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        self.turn_restrictions.insert((at, from, to));
+        self.invalidate_paths();
+        Ok(())
+    }
+
+    /// Returns `true` if `end` can be reached from `start`.
+    ///
+    /// Answered from connectivity that is kept up to date while the maze is edited,
+    /// so it doesn't require running the search. Connectivity is built from the same neighbours
+    /// the search expands, so both agree on fields that are only connected diagonally or through a portal.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, Movement};
+    ///
+    /// let mut maze = Maze::from_lines(&["SW", "WE"]).unwrap();
+    ///
+    /// assert!(maze.is_solvable());
+    /// assert!(maze.reachable_region((0, 0)).unwrap().contains(&(1, 1)));
+    /// assert!(maze.try_solve().is_ok());
+    ///
+    /// // Diagonal gap can't be passed moving in four directions.
+    /// let mut maze = maze.set_movement(Movement::FourWay);
+    ///
+    /// assert!(!maze.is_solvable());
+    /// assert!(!maze.reachable_region((0, 0)).unwrap().contains(&(1, 1)));
+    /// assert!(maze.try_solve().is_err());
+    /// ```
+    pub fn is_solvable(&self) -> bool {
+        match (self.start, self.end, &self.connectivity) {
+            (Some(start), Some(end), Some(connectivity)) => connectivity.connected(start, end),
+            _ => false,
+        }
+    }
+
+    /// Returns cost of the shortest path between `a` and `b` (`(x, y)`), without storing the path.
+    ///
+    /// Straight step costs `10`, diagonal `14`, plus any entry and climbing costs of the fields.
+    ///
+    /// # Errors
+    /// If either position is outside of the maze.
+    ///
+    /// If `b` can't be reached from `a`.
+    ///
+    /// # Example
+    /// `b` can lie on any side of `a`, e.g. above and to the left of it.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["E...", ".W..", "...S"]).unwrap();
+    ///
+    /// assert_eq!(maze.cost_between((3, 2), (0, 0)).unwrap(), 38);
+    /// assert_eq!(maze.cost_between((0, 0), (3, 2)).unwrap(), 38);
+    /// ```
+    ///
+    /// Cost is the sum of the steps along the shortest path.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let file = std::env::temp_dir().join("astar_cost_between_costs.txt");
+    /// std::fs::write(&file, "0510\n0230\n").unwrap();
+    /// let costs = [[0, 5, 1, 0], [0, 2, 3, 0]];
+    ///
+    /// let mut maze = Maze::from_lines(&["S...", "...E"])
+    ///     .unwrap()
+    ///     .set_cost_file(file.to_str().unwrap())
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = maze.get_path().unwrap();
+    /// let summed: usize = path
+    ///     .windows(2)
+    ///     .map(|step| {
+    ///         let ((ax, ay), (bx, by)) = (step[0], step[1]);
+    ///         let base = if ax != bx && ay != by { 14 } else { 10 };
+    ///         base + costs[by][bx] * 10
+    ///     })
+    ///     .sum();
+    /// assert_eq!(maze.cost_between((0, 0), (3, 1)).unwrap(), summed);
+    /// ```
+    pub fn cost_between(&self, a: (usize, usize), b: (usize, usize)) -> Result<usize> {
+        let (a, b) = (Position(a), Position(b));
+        if !self.in_bounds(a) || !self.in_bounds(b) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        self.search(
+            a,
+            |position| position == b,
+            |position| self.heuristic(position, b),
+        )
+        .map(|goal| goal.g_cost)
+        .ok_or(MazeIsNotSolvable.into())
+    }
+
+    /// Returns how many distinct shortest paths lead from `start` to `end`.
+    ///
+    /// Count saturates at [`usize::MAX`] instead of overflowing on large open areas.
+    ///
+    /// # Errors
+    /// If `start`/`end` are not set.
+    ///
+    /// If `end` can't be reached from `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Two straight steps and one diagonal, the diagonal can be taken first, second or last.
+    /// let maze = Maze::from_lines(&["S...", "...E"]).unwrap();
+    /// assert_eq!(maze.optimal_path_count().unwrap(), 3);
+    /// ```
+    pub fn optimal_path_count(&self) -> Result<usize> {
+        let (Some(start), Some(end)) = (self.start, self.end) else {
+            return Err(StartEndNotSet.into());
+        };
+
+        let costs = self.cost_field(start);
+        if !costs.contains_key(&end) {
+            return Err(MazeIsNotSolvable.into());
+        }
+
+        let mut order: Vec<_> = costs
+            .iter()
+            .map(|(position, cost)| (*cost, *position))
+            .collect();
+        order.sort_unstable_by_key(|(cost, position)| (*cost, position.xy_usize()));
+
+        let mut counts = HashMap::from([(start, 1usize)]);
+        for (cost, position) in order {
+            let count = counts.get(&position).copied().unwrap_or(0);
+            for neighbour in self.steps(position) {
+                if costs.get(&neighbour) == Some(&(cost + self.move_cost(position, neighbour))) {
+                    let entry = counts.entry(neighbour).or_insert(0);
+                    *entry = entry.saturating_add(count);
+                }
+            }
+        }
+        Ok(counts[&end])
+    }
+
+    /// Returns cost of the shortest path between every pair of `cells` (`(x, y)`), running Dijkstra from each of them.
+    ///
+    /// `matrix[i][j]` is the cost from `cells[i]` to `cells[j]`, [`None`] if it can't be reached.
+    /// Matrix is symmetric unless elevation is set.
+    ///
+    /// # Errors
+    /// If any of the `cells` is outside of the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["....", "....", "...."]).unwrap();
+    /// let matrix = maze.distance_matrix(&[(0, 0), (3, 0), (0, 2), (3, 2)]).unwrap();
+    ///
+    /// assert_eq!(matrix[0][3], Some(38));
+    /// for i in 0..4 {
+    ///     assert_eq!(matrix[i][i], Some(0));
+    ///     for j in 0..4 {
+    ///         assert_eq!(matrix[i][j], matrix[j][i]);
+    ///     }
+    /// }
+    /// ```
+    pub fn distance_matrix(&self, cells: &[(usize, usize)]) -> Result<Vec<Vec<Option<usize>>>> {
+        if cells.iter().any(|cell| !self.in_bounds(Position(*cell))) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        Ok(cells
+            .iter()
+            .map(|from| {
+                let costs = self.cost_field(Position(*from));
+                cells
+                    .iter()
+                    .map(|to| costs.get(&Position(*to)).copied())
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Returns every walkable neighbour of `position` (`(x, y)`) paired with the cost of stepping onto it.
+    ///
+    /// Straight step costs `10` and diagonal `14`, entry costs of the neighbour are included.
+    /// This is the same edge model the search uses.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S.W", "...", "..E"]).unwrap();
+    ///
+    /// let mut neighbors = maze.neighbors_with_cost((0, 0));
+    /// neighbors.sort();
+    /// assert_eq!(neighbors, vec![((0, 1), 10), ((1, 0), 10), ((1, 1), 14)]);
+    /// ```
+    pub fn neighbors_with_cost(&self, position: (usize, usize)) -> Vec<((usize, usize), usize)> {
+        let position = Position(position);
+        if !self.in_bounds(position) {
+            return vec![];
+        }
+
+        self.steps(position)
+            .into_iter()
+            .map(|neighbour| (neighbour.xy_usize(), self.move_cost(position, neighbour)))
+            .collect()
+    }
+
+    /// Returns fields that can be entered from `position` (`(x, y)`) in a single step and are not in `visited`.
+    ///
+    /// Steps are the same the search takes, portals included. Building block for searches driven step by step from outside.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    /// use std::collections::HashSet;
+    ///
+    /// let maze = Maze::from_lines(&["S.", "WE"]).unwrap();
+    /// let visited = HashSet::from([(1, 0)]);
+    ///
+    /// assert_eq!(maze.neighbors_unvisited((0, 0), &HashSet::new()), vec![(1, 0), (1, 1)]);
+    /// assert_eq!(maze.neighbors_unvisited((0, 0), &visited), vec![(1, 1)]);
+    /// ```
+    pub fn neighbors_unvisited(
+        &self,
+        position: (usize, usize),
+        visited: &HashSet<(usize, usize)>,
+    ) -> Vec<(usize, usize)> {
+        let position = Position(position);
+        if !self.in_bounds(position) {
+            return vec![];
+        }
+
+        self.steps(position)
+            .into_iter()
+            .map(|neighbour| neighbour.xy_usize())
+            .filter(|neighbour| !visited.contains(neighbour))
+            .collect()
+    }
+
+    /// Returns walkable fields north, east, south and west of `position` (`(x, y)`), in that order.
+    ///
+    /// Only walls and bounds of the maze are considered, regardless of how the search moves (portals are not included).
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S.W", ".W.", "..E"]).unwrap();
+    /// assert_eq!(maze.neighbors_4((1, 1)), vec![(1, 0), (2, 1), (1, 2), (0, 1)]);
+    /// assert_eq!(maze.neighbors_4((3, 3)), vec![]);
+    /// ```
+    pub fn neighbors_4(&self, position: (usize, usize)) -> Vec<(usize, usize)> {
+        self.adjacent_fields(Position(position), false)
+    }
+
+    /// Returns walkable fields around `position` (`(x, y)`) including diagonal ones, clockwise starting from north.
+    ///
+    /// Only walls and bounds of the maze are considered, regardless of how the search moves (portals are not included).
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S.W", ".W.", "..E"]).unwrap();
+    /// let neighbors = vec![(1, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1), (0, 0)];
+    /// assert_eq!(maze.neighbors_8((1, 1)), neighbors);
+    /// ```
+    pub fn neighbors_8(&self, position: (usize, usize)) -> Vec<(usize, usize)> {
+        self.adjacent_fields(Position(position), true)
+    }
+
+    /// Returns the first wall field (`(x, y)`) hit by a ray cast from `from` in `direction`.
+    ///
+    /// Field at `from` itself is not checked. Returns [`None`] if the ray leaves the maze without hitting a wall.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze};
+    ///
+    /// let maze = Maze::from_lines(&["S...", "..W.", "...E"]).unwrap();
+    ///
+    /// assert_eq!(maze.raycast((0, 1), Direction::East), Some((2, 1)));
+    /// assert_eq!(maze.raycast((3, 2), Direction::NorthWest), Some((2, 1)));
+    /// // Ray leaves the maze through open fields.
+    /// assert_eq!(maze.raycast((0, 0), Direction::East), None);
+    /// assert_eq!(maze.raycast((0, 0), Direction::North), None);
+    /// ```
+    pub fn raycast(&self, from: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (offset_x, offset_y) = direction.offset();
+        let mut position = Position(from);
+
+        loop {
+            let x = position.0 .0.checked_add_signed(offset_x)?;
+            let y = position.0 .1.checked_add_signed(offset_y)?;
+            position = Position((x, y));
+            if !self.in_bounds(position) {
+                return None;
+            }
+            if self.maze[y][x] == self.wall_char {
+                return Some((x, y));
+            }
+        }
+    }
+
+    /// Returns `true` if `b` can be entered from `a` (`(x, y)`) in a single step, using the same movement rules as the search.
+    pub fn are_adjacent(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let a = Position(a);
+        self.in_bounds(a)
+            && self
+                .steps(a)
+                .into_iter()
+                .any(|neighbour| neighbour == Position(b))
+    }
+
+    /// Checks that `path` (`(x, y)`) starts on a walkable field and every step of it is legal, see `are_adjacent`.
+    ///
+    /// # Errors
+    /// If the first field is not walkable or a step goes to a field that is not adjacent or walkable.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    ///
+    /// assert!(maze.validate_path(&[(0, 0), (1, 0), (2, 1), (2, 2)]).is_ok());
+    /// // Diagonal step onto a wall.
+    /// assert!(maze.validate_path(&[(0, 0), (1, 1), (2, 2)]).is_err());
+    /// // Gap between fields.
+    /// assert!(maze.validate_path(&[(0, 0), (2, 0), (2, 1), (2, 2)]).is_err());
+    /// ```
+    pub fn validate_path(&self, path: &[(usize, usize)]) -> Result<()> {
+        let first_blocked = path
+            .first()
+            .is_some_and(|first| !self.is_walkable(Position(*first)));
+        let illegal_step = path
+            .windows(2)
+            .any(|step| !self.are_adjacent(step[0], step[1]));
+
+        if first_blocked || illegal_step {
+            return Err(InvalidPath.into());
+        }
+        Ok(())
+    }
+
+    /// Returns every walkable field that can be reached from `from` (`(x, y)`), including `from` itself.
+    ///
+    /// Uses the same movement rules as the search. Region of a wall field is empty.
+    ///
+    /// # Errors
+    /// If `from` is outside of the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Two rooms separated by a wall.
+    /// let maze = Maze::from_lines(&["S.W..", "..W..", "..W.E"]).unwrap();
+    ///
+    /// let left = maze.reachable_region((0, 0)).unwrap();
+    /// let right = maze.reachable_region((4, 2)).unwrap();
+    /// assert_eq!((left.len(), right.len()), (6, 6));
+    /// assert!(left.is_disjoint(&right));
+    /// assert!(maze.reachable_region((2, 1)).unwrap().is_empty());
+    /// ```
+    pub fn reachable_region(&self, from: (usize, usize)) -> Result<HashSet<(usize, usize)>> {
+        let from = Position(from);
+        if !self.in_bounds(from) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        Ok(self
+            .flood_fill(from)
+            .into_iter()
+            .map(|position| position.xy_usize())
+            .collect())
+    }
+
+    /// Returns every pair of walkable fields (`(x, y)`) that can be reached from each other.
+    ///
+    /// Each pair is listed once, its first field comes earlier in row by row order. Answered from
+    /// connectivity (see `is_solvable`), so no search is run.
+    ///
+    /// # Errors
+    /// If the maze has more than 1024 walkable fields, the number of pairs would grow too large.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Two rooms separated by a wall.
+    /// let maze = Maze::from_lines(&["..W.", "WWW."]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     maze.all_solvable_pairs().unwrap(),
+    ///     vec![((0, 0), (1, 0)), ((3, 0), (3, 1))]
+    /// );
+    /// ```
+    pub fn all_solvable_pairs(&self) -> Result<Vec<(Field, Field)>> {
+        let fields: Vec<Position> = self.walkable_fields().collect();
+        if fields.len() > MAX_PAIR_FIELDS {
+            return Err(TooManyFields.into());
+        }
+
+        let connected = |a, b| {
+            self.connectivity
+                .as_ref()
+                .is_some_and(|connectivity| connectivity.connected(a, b))
+        };
+
+        let mut pairs = vec![];
+        for (i, &a) in fields.iter().enumerate() {
+            for &b in &fields[i + 1..] {
+                if connected(a, b) {
+                    pairs.push((a.xy_usize(), b.xy_usize()));
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Returns a pseudo random walkable field, the same `seed` always picks the same field.
+    ///
+    /// Returns [`None`] if there are no walkable fields.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let maze = Maze::from_lines(&["S.W.W", "WW.W.", ".W.WE"]).unwrap();
+    /// let cell = maze.random_open_cell(42).unwrap();
+    ///
+    /// assert_eq!(maze.random_open_cell(42), Some(cell));
+    /// assert_ne!(maze.field()[cell.1][cell.0], maze.wall());
+    /// assert_eq!(Maze::from_lines(&["WW"]).unwrap().random_open_cell(42), None);
+    /// ```
+    pub fn random_open_cell(&self, seed: u64) -> Option<(usize, usize)> {
+        let fields = self.walkable_fields().collect::<Vec<_>>();
+        if fields.is_empty() {
+            return None;
+        }
+
+        let index = Rng::new(seed).below(fields.len());
+        Some(fields[index].xy_usize())
+    }
+
+    /// Returns a pair of walkable fields whose shortest path is the longest, together with the cost of that path.
+    ///
+    /// This is a double sweep approximation: first sweep finds the farthest field from `start`
+    /// (or from the first walkable field if start is not set), second sweep finds the farthest field from that one.
+    /// It is exact for tree-like mazes, on open areas it can underestimate the true diameter.
+    ///
+    /// # Errors
+    /// If there are no walkable fields in the maze.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Both ends of the winding corridor lie on the periphery, whatever field the sweep starts from.
+    /// let maze = Maze::try_from("......\nWWWWW.\n......\n.WWWWW\n......").unwrap();
+    /// assert_eq!(maze.diameter().unwrap(), ((5, 4), (0, 0), 166));
+    ///
+    /// let maze = Maze::try_from("..S...\nWWWWW.\n......").unwrap();
+    /// let (a, b, cost) = maze.diameter().unwrap();
+    /// assert_eq!((a, b), ((0, 2), (0, 0)));
+    /// assert_eq!(cost, 108);
+    /// ```
+    pub fn diameter(&self) -> Result<(Field, Field, usize)> {
+        let seed = self
+            .start
+            .filter(|start| self.is_walkable(*start))
+            .or_else(|| self.walkable_fields().next())
+            .ok_or(Error::from(MazeIsNotSolvable))?;
+
+        let farthest = |from: Position| {
+            self.cost_field(from)
+                .into_iter()
+                .max_by_key(|(position, cost)| {
+                    (*cost, Reverse(position.0 .1), Reverse(position.0 .0))
+                })
+                .unwrap_or((from, 0))
+        };
+
+        let (a, _) = farthest(seed);
+        let (b, cost) = farthest(a);
+
+        Ok((a.xy_usize(), b.xy_usize(), cost))
+    }
+
+    /// Returns a pair of walkable fields whose shortest path has close to `target_len` fields (including both ends).
+    ///
+    /// A fixed number of pseudo random pairs is sampled and the closest one is returned, the same `seed` always
+    /// returns the same pair. Returns [`None`] if no sampled pair is connected.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let rows = vec!["..........", "..WWWWWW..", ".........."];
+    /// let mut maze = Maze::from_lines(&rows).unwrap();
+    ///
+    /// let (a, b) = maze.choose_endpoints_for_length(8, 7).unwrap();
+    /// let len = maze.solve_between(a, b).unwrap().len();
+    /// assert!(len.abs_diff(8) <= 1);
+    /// ```
+    pub fn choose_endpoints_for_length(
+        &self,
+        target_len: usize,
+        seed: u64,
+    ) -> Option<(Field, Field)> {
+        const SAMPLES: usize = 64;
+
+        let fields = self.walkable_fields().collect::<Vec<_>>();
+        if fields.is_empty() {
+            return None;
+        }
+
+        let mut rng = Rng::new(seed);
+        let mut best: Option<(usize, Field, Field)> = None;
+        for _ in 0..SAMPLES {
+            let a = fields[rng.below(fields.len())];
+            let b = fields[rng.below(fields.len())];
+            let Some(goal) = self.search(a, |p| p == b, |p| self.heuristic(p, b)) else {
+                continue;
+            };
+
+            let miss = goal.trace().len().abs_diff(target_len);
+            if best.is_none_or(|(best_miss, _, _)| miss < best_miss) {
+                best = Some((miss, a.xy_usize(), b.xy_usize()));
+            }
+        }
+
+        best.map(|(_, a, b)| (a, b))
+    }
+
+    /// Sets function returning the cost of a step from a field to its neighbour (both `(x, y)`), [`None`] if the step is blocked.
+    ///
+    /// Returned cost replaces the step, entry and climbing costs, `10` is the cost of a plain straight step.
+    /// Walls still block, portals keep their own cost. Since the costs can be arbitrarily low, the heuristic
+    /// is turned off and the search expands as many nodes as Dijkstra. Ignored by `set_resolution`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// // Moving along the bottom row is almost free.
+    /// let mut maze = Maze::from_lines(&[".......", "S.....E", "......."])
+    ///     .unwrap()
+    ///     .set_cost_fn(Box::new(|(ax, ay), (bx, by)| match (ay, by) {
+    ///         (2, 2) => Some(1),
+    ///         _ if ax != bx && ay != by => Some(14),
+    ///         _ => Some(10),
+    ///     }));
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = vec![(0, 1), (0, 2), (1, 2), (2, 2), (3, 2), (4, 2), (5, 2), (6, 2), (6, 1)];
+    /// assert_eq!(maze.get_path().unwrap(), path);
+    /// assert_eq!(maze.get_path_cost().unwrap(), 26.0);
+    ///
+    /// // Even a single step to `end` is priced by the function.
+    /// let mut maze = Maze::from_lines(&["S.", ".E"])
+    ///     .unwrap()
+    ///     .set_cost_fn(Box::new(|(ax, ay), (bx, by)| {
+    ///         if ax != bx && ay != by {
+    ///             Some(1000)
+    ///         } else {
+    ///             Some(1)
+    ///         }
+    ///     }));
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 3);
+    /// assert_eq!(maze.get_path_cost().unwrap(), 2.0);
+    /// ```
+    pub fn set_cost_fn(mut self, cost_fn: Box<CostFn>) -> Self {
+        self.cost_fn = Some(cost_fn);
+        self.invalidate_paths();
+        self.update_connectivity();
+        self
+    }
+
+    /// Sets directions the path can move in, [`Movement::EightWay`] by default.
+    ///
+    /// Applies to every search and to reachability queries. Helpers describing the surroundings of a field
+    /// (`neighbors_4`, `neighbors_8`, `raycast`) don't depend on it.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, Movement};
+    ///
+    /// let solve = |movement| {
+    ///     let mut maze = Maze::from_lines(&["S..", "...", "..E"])
+    ///         .unwrap()
+    ///         .set_movement(movement);
+    ///     maze.try_solve().unwrap();
+    ///     (maze.get_path().unwrap().len(), maze.get_path_cost().unwrap())
+    /// };
+    ///
+    /// assert_eq!(solve(Movement::EightWay), (3, 28.0));
+    /// assert_eq!(solve(Movement::FourWay), (5, 40.0));
+    /// ```
+    pub fn set_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self.invalidate_paths();
+        self.update_connectivity();
+        self
+    }
+
+    /// Sets heuristic estimating the remaining cost of the path, [`Heuristic::Euclidean`] by default.
+    ///
+    /// See [`Heuristic`] for which of them keep the found path the shortest.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Heuristic, Maze};
+    ///
+    /// let solve = |heuristic| {
+    ///     let mut maze = Maze::from_lines(&["S.........", "..........", ".........E"])
+    ///         .unwrap()
+    ///         .set_heuristic(heuristic);
+    ///     maze.try_solve().unwrap();
+    ///     (maze.get_path_cost().unwrap(), maze.solve_stats().unwrap().expanded)
+    /// };
+    ///
+    /// let (euclidean_cost, euclidean_expanded) = solve(Heuristic::Euclidean);
+    /// let (octile_cost, octile_expanded) = solve(Heuristic::Octile);
+    /// assert_eq!(octile_cost, euclidean_cost);
+    /// assert!(octile_expanded <= euclidean_expanded);
+    /// ```
+    pub fn set_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets function breaking ties between open nodes with equal `f_cost`.
+    ///
+    /// Nodes are still expanded in order of `f_cost`, so the found path stays the shortest.
+    /// Among nodes with equal `f_cost`, the one ordered first ([`Ordering::Less`](std::cmp::Ordering::Less)) is expanded first.
+    ///
+    /// # Examples
+    /// ```
+    /// use astar::{Error, Maze};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     // Prefer nodes further from the start (higher `g_cost`).
+    ///     let mut maze = Maze::try_from("S...\n....\n...E")?
+    ///         .set_tiebreak_fn(Box::new(|a, b| b.g_cost().cmp(&a.g_cost())));
+    ///
+    ///     maze.try_solve()?;
+    ///     assert_eq!(maze.get_path()?.len(), 4);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_tiebreak_fn(mut self, tiebreak: Box<TieBreak>) -> Self {
+        self.tiebreak = Some(tiebreak);
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets whether `try_solve` (and its variants) use float costs, straight step costs `1.0` and diagonal `√2`.
+    ///
+    /// Entry and climbing costs are scaled the same way, each unit costs `1.0` instead of `10`.
+    /// Cost cap of `try_solve_within_cost` stays in integer units (`10` per straight step).
+    /// Other methods returning costs are not affected, use `get_path_cost` for the cost of the solved path.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S...", "....", "....", "...E"])
+    ///     .unwrap()
+    ///     .set_float_costs(true);
+    /// maze.try_solve().unwrap();
+    ///
+    /// let cost = maze.get_path_cost().unwrap();
+    /// assert!((cost - 3.0 * 2f64.sqrt()).abs() < 1e-9);
+    /// ```
+    pub fn set_float_costs(mut self, enabled: bool) -> Self {
+        self.float_costs = enabled;
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets how many times each field is subdivided along both axes when solving with `try_solve` (or its variants).
+    ///
+    /// With `factor` above `1` the search runs on a grid where every field becomes `factor` x `factor` fields,
+    /// walls stay walls and open fields stay open. Movement inside a field is finer, so the path can follow
+    /// straight lines more closely. Found path is mapped back to fields of the original maze.
+    ///
+    /// Entry and climbing costs are added once per original field entered, cost cap and step limit apply
+    /// to the subdivided grid. Float costs, portals and capacities above zero are not used in this mode.
+    /// `factor` of `0` is treated as `1`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S...", "WWW.", "E..."]).unwrap().set_resolution(2);
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = maze.get_path().unwrap();
+    /// assert_eq!(path.first(), Some(&(0, 0)));
+    /// assert_eq!(path.last(), Some(&(0, 2)));
+    /// assert!(maze.validate_path(&path).is_ok());
+    /// ```
+    pub fn set_resolution(mut self, factor: usize) -> Self {
+        self.resolution = factor.max(1);
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets whether ties between nodes with equal `f_cost` prefer nodes closer to the straight line from start to end.
+    ///
+    /// Applied before the function set by `set_tiebreak_fn`. It only changes the order of equally promising nodes,
+    /// so the path stays the shortest, but on open areas fewer nodes are expanded.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut rows = vec![".".repeat(40); 30];
+    /// rows[2].replace_range(1..2, "S");
+    /// rows[25].replace_range(37..38, "E");
+    /// let lines: Vec<&str> = rows.iter().map(String::as_str).collect();
+    ///
+    /// let solve = |cross| {
+    ///     let mut maze = Maze::from_lines(&lines).unwrap().set_cross_tiebreak(cross);
+    ///     maze.try_solve().unwrap();
+    ///     let path = maze.get_path().unwrap();
+    ///     let cost: usize = path
+    ///         .windows(2)
+    ///         .map(|step| maze.cost_between(step[0], step[1]).unwrap())
+    ///         .sum();
+    ///     (cost, maze.solve_stats().unwrap().expanded)
+    /// };
+    ///
+    /// let (plain_cost, plain_expanded) = solve(false);
+    /// let (cross_cost, cross_expanded) = solve(true);
+    /// assert_eq!(plain_cost, cross_cost);
+    /// assert!(cross_expanded < plain_expanded);
+    /// ```
+    pub fn set_cross_tiebreak(mut self, enabled: bool) -> Self {
+        self.cross_tiebreak = enabled;
+        self.invalidate_paths();
+        self
+    }
+
+    /// Sets data structure holding the open set of `try_solve` (and its variants), [`QueueImpl::Binary`] by default.
+    ///
+    /// Costs of the maze are small integers, so a [`QueueImpl::Bucket`] queue can push nodes without
+    /// comparing them, which pays off on large mazes. Found path is the same with either queue.
+    /// Float costs (see `set_float_costs`) are too fine grained for buckets and always use the binary heap.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, QueueImpl};
+    ///
+    /// let lines = ["S...W.....", ".WW.W.WWW.", ".W..W...W.", ".W.WWWW.W.", "...1.....E"];
+    /// let mut binary = Maze::from_lines(&lines).unwrap();
+    /// let mut bucket = Maze::from_lines(&lines)
+    ///     .unwrap()
+    ///     .set_queue_impl(QueueImpl::Bucket);
+    ///
+    /// binary.try_solve().unwrap();
+    /// bucket.try_solve().unwrap();
+    /// assert_eq!(bucket.get_path().unwrap(), binary.get_path().unwrap());
+    /// assert_eq!(bucket.get_path_cost().unwrap(), binary.get_path_cost().unwrap());
+    /// ```
+    pub fn set_queue_impl(mut self, queue: QueueImpl) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// Sets whether every solve first checks that the heuristic is admissible, disabled by default.
+    ///
+    /// Before searching, cost of the shortest path to the end is computed for every field with a backwards Dijkstra,
+    /// the heuristic must not exceed it anywhere, otherwise the found path might not be the shortest.
+    /// The heuristic is checked against movement, entry, climbing and portal costs, field capacities are ignored.
+    /// This makes each solve considerably slower, so it is meant for debugging.
+    ///
+    /// Inflated heuristic of `try_solve_anytime` is intentionally inadmissible, only its base is checked.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Heuristic, Maze, Movement};
+    ///
+    /// // Portal makes the end much cheaper to reach than straight line distance suggests.
+    /// let mut maze = Maze::from_lines(&["S........E"])
+    ///     .unwrap()
+    ///     .set_portal((0, 0), (9, 0), 1)
+    ///     .set_validate_admissibility(true);
+    ///
+    /// assert!(maze.try_solve().is_ok());
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (9, 0)]);
+    ///
+    /// // Euclidean distance never overestimates, not even with four-way movement,
+    /// // but Manhattan distance does once diagonal steps are allowed.
+    /// let mut maze = Maze::from_lines(&["S..", "...", "..E"])
+    ///     .unwrap()
+    ///     .set_heuristic(Heuristic::Manhattan)
+    ///     .set_movement(Movement::EightWay)
+    ///     .set_validate_admissibility(true);
+    ///
+    /// let error = maze.try_solve().err().unwrap();
+    /// assert!(error.to_string().contains("overestimates"));
+    /// ```
+    pub fn set_validate_admissibility(mut self, enabled: bool) -> Self {
+        self.validate_admissibility = enabled;
+        self
+    }
+
+    /// Returns statistics of the last `try_solve` (or one of its variants), [`None`] if the maze was never solved.
+    pub fn solve_stats(&self) -> Option<SolveStats> {
+        self.stats
+    }
+
+    /// Sets the path in our maze (tries to solve the maze).
+    ///
+    /// # Errors
+    /// If symbols for `start`/`end` are not found inside the text file.
+    ///
+    /// If it is impossible to solve the maze.
+    ///
+    /// If `start`, `end`, `separator` or `wall` share the same character, it will also return [`Error`].
+    ///
+    /// # Example
+    /// If `end` can be entered straight from `start`, that single step is the shortest path and no nodes are expanded.
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.", ".E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1)]);
+    /// assert_eq!(maze.solve_stats().unwrap().expanded, 0);
+    ///
+    /// // The step must still be allowed.
+    /// maze.set_wall_between((0, 0), (1, 1)).unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 3);
+    ///
+    /// // And still the cheapest, a free portal next to start makes the detour cheaper.
+    /// let mut maze = Maze::from_lines(&["S.", ".E"]).unwrap().set_portal((0, 0), (1, 0), 0);
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (1, 1)]);
+    /// assert_eq!(maze.get_path_cost().unwrap(), 10.0);
+    /// ```
+    pub fn try_solve(&mut self) -> Result<()> {
+        self.solve_with(Limits::default())
+    }
+
+    /// Same as `try_solve`, but also returns how long solving took.
+    ///
+    /// # Errors
+    /// Same as `try_solve`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut rows = vec![".".repeat(100); 100];
+    /// rows[0].replace_range(..1, "S");
+    /// rows[99].replace_range(99.., "E");
+    /// let lines = rows.iter().map(String::as_str).collect::<Vec<_>>();
+    ///
+    /// let mut maze = Maze::from_lines(&lines).unwrap();
+    /// let elapsed = maze.try_solve_timed().unwrap();
+    ///
+    /// assert!(!elapsed.is_zero());
+    /// assert_eq!(maze.get_path().unwrap().len(), 100);
+    /// ```
+    pub fn try_solve_timed(&mut self) -> Result<Duration> {
+        let now = Instant::now();
+        self.try_solve()?;
+        Ok(now.elapsed())
+    }
+
+    /// Same as `try_solve`, but only accepts paths whose total cost is at most `max_cost`.
+    ///
+    /// Nodes whose `f_cost` exceeds the cap are pruned from the search.
+    ///
+    /// # Errors
+    /// Same as `try_solve`, [`Error`] is also returned if every path costs more than `max_cost`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::try_from("S..\nWW.\nE..").unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Path found before doesn't survive a failed search.
+    /// assert!(maze.try_solve_within_cost(30).is_err());
+    /// assert!(maze.get_path().is_err());
+    /// assert!(maze.solve_stats().is_some());
+    ///
+    /// maze.try_solve_within_cost(48).unwrap();
+    /// assert_eq!(maze.get_path().unwrap().len(), 5);
+    /// ```
+    pub fn try_solve_within_cost(&mut self, max_cost: usize) -> Result<()> {
+        self.solve_with(Limits {
+            max_cost: Some(max_cost),
+            ..Limits::default()
+        })
+    }
+
+    /// Same as `try_solve`, but only accepts paths made of at most `max_steps` fields (start and end included).
+    ///
+    /// The number of steps becomes part of the search state, so a cheaper path isn't allowed
+    /// to hide a more expensive one that fits within the limit.
+    ///
+    /// # Errors
+    /// Same as `try_solve`, [`Error`] is also returned if every path is longer than `max_steps`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Shortest path exceeds the step limit.
+    /// assert!(maze.try_solve_max_steps(2).is_err());
+    /// assert!(maze.get_path().is_err());
+    ///
+    /// maze.try_solve_max_steps(3).unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (2, 0)]);
+    /// ```
+    pub fn try_solve_max_steps(&mut self, max_steps: usize) -> Result<()> {
+        self.solve_with(Limits {
+            max_steps: Some(max_steps),
+            ..Limits::default()
+        })
+    }
+
+    /// Same as `try_solve`, but the search stops as soon as `cancel` is set, so it can be cancelled from another thread.
+    ///
+    /// The flag is checked before each node is expanded. A path found before the flag was set is kept.
+    ///
+    /// # Errors
+    /// Same as `try_solve`, [`Error`] is also returned if the search was cancelled before reaching the end.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// let mut maze = Maze::random_obstacles(200, 200, 0.2, 7);
+    ///
+    /// let cancel = AtomicBool::new(true);
+    /// let error = maze.try_solve_cancellable(&cancel).unwrap_err();
+    /// assert_eq!(error.to_string(), "Search was cancelled.");
+    /// assert_eq!(maze.solve_stats().unwrap().expanded, 0);
+    ///
+    /// // Even if the end is a single step away.
+    /// let mut maze = Maze::from_lines(&["SE"]).unwrap();
+    /// assert!(maze.try_solve_cancellable(&cancel).is_err());
+    /// ```
+    pub fn try_solve_cancellable(&mut self, cancel: &AtomicBool) -> Result<()> {
+        self.solve_with(Limits {
+            cancel: Some(cancel),
+            ..Limits::default()
+        })
+    }
+
+    /// Same as `try_solve`, but sends every expanded field (`(x, y)`) through `tx` as the search goes,
+    /// so another thread can show its progress.
+    ///
+    /// Fields are sent in the order they are expanded, starting with the start. Nothing is sent if the end is
+    /// a single step from the start, and fields can repeat if `set_resolution` is used.
+    /// Sending stops silently once the receiver is dropped, the search continues.
+    ///
+    /// # Errors
+    /// Same as `try_solve`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    /// use std::sync::mpsc;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    /// let (tx, rx) = mpsc::channel();
+    /// maze.try_solve_channel(tx).unwrap();
+    ///
+    /// let expanded: Vec<(usize, usize)> = rx.iter().collect();
+    /// assert_eq!(expanded.first(), Some(&(0, 0)));
+    /// assert_eq!(expanded.len(), maze.solve_stats().unwrap().expanded);
+    /// ```
+    pub fn try_solve_channel(&mut self, tx: Sender<(usize, usize)>) -> Result<()> {
+        let send = |position: Position| {
+            let _ = tx.send(position.xy_usize());
+        };
+        self.solve_with(Limits {
+            on_expand: Some(&send),
+            ..Limits::default()
+        })
+    }
+
+    /// Same as `try_solve`, but expands at most `max_steps` nodes, so a long search can be spread over multiple calls
+    /// (e.g. frames of a game loop).
+    ///
+    /// If the budget runs out first, [`SolveProgress::InProgress`] holds open and closed sets of the search,
+    /// continue it by passing them to `resume`. Once the path is found it is stored the same way as by `try_solve`.
+    /// `max_steps` of `0` are treated as `1`.
+    ///
+    /// # Errors
+    /// If `start`/`end` are not set or symbols are not unique, unsolvable maze is reported as [`SolveProgress::Unsolvable`].
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, SolveProgress};
+    ///
+    /// let lines = ["S.....", ".WWWW.", ".W..W.", ".W.EW.", ".W..W.", "......"];
+    /// let mut single = Maze::from_lines(&lines).unwrap();
+    /// single.try_solve().unwrap();
+    ///
+    /// let mut maze = Maze::from_lines(&lines).unwrap();
+    /// let mut progress = maze.solve_budgeted(4).unwrap();
+    /// let mut calls = 1;
+    /// while let SolveProgress::InProgress(state) = progress {
+    ///     progress = maze.resume(state, 4).unwrap();
+    ///     calls += 1;
+    /// }
+    ///
+    /// assert!(calls > 1);
+    /// assert!(matches!(progress, SolveProgress::Done(path) if path == single.get_path().unwrap()));
+    /// assert_eq!(maze.solve_stats(), single.solve_stats());
+    /// ```
+    pub fn solve_budgeted(&mut self, max_steps: usize) -> Result<SolveProgress> {
+        self.run_search(
+            Limits {
+                budget: Some(max_steps.max(1)),
+                ..Limits::default()
+            },
+            1.0,
+            None,
+        )
+    }
+
+    /// Continues the search paused by `solve_budgeted`, expanding at most `max_steps` more nodes.
+    ///
+    /// `state` must come from this maze and the maze must not be changed in between,
+    /// otherwise the result is unspecified.
+    ///
+    /// # Errors
+    /// Same as `solve_budgeted`.
+    pub fn resume(&mut self, state: SolveState, max_steps: usize) -> Result<SolveProgress> {
+        self.run_search(
+            Limits {
+                budget: Some(max_steps.max(1)),
+                ..Limits::default()
+            },
+            1.0,
+            Some(state),
+        )
+    }
+
+    /// Same as `try_solve`, but runs up to `iterations` searches with the heuristic inflated by a decreasing weight
+    /// and keeps the cheapest path found.
+    ///
+    /// Weights start at `3.0` and decrease by `0.5` each iteration down to `1.0`, where the path is the shortest.
+    /// Inflated searches expand fewer nodes and find a path sooner, so more iterations mean more time and a path that
+    /// costs the same or less. From the fifth iteration on the path is always the shortest.
+    /// Each iteration is a separate search, nodes aren't reused between them.
+    ///
+    /// # Errors
+    /// Same as `try_solve`, `iterations` of `0` are treated as `1`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&[
+    ///     "S.........",
+    ///     ".WWWWWWW..",
+    ///     ".......W..",
+    ///     "WWWWWW.W..",
+    ///     "......W..E",
+    /// ])
+    /// .unwrap();
+    ///
+    /// let mut previous = f64::INFINITY;
+    /// for iterations in 1..=6 {
+    ///     maze.try_solve_anytime(iterations).unwrap();
+    ///     let cost = maze.get_path_cost().unwrap();
+    ///     assert!(cost <= previous);
+    ///     previous = cost;
+    /// }
+    ///
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path_cost().unwrap(), previous);
+    /// ```
+    pub fn try_solve_anytime(&mut self, iterations: usize) -> Result<()> {
+        let avoid = self.avoid.clone();
+        let mut best: Option<(f64, Path)> = None;
+
+        for iteration in 0..iterations.max(1) {
+            let weight = (3.0 - 0.5 * iteration as f64).max(1.0);
+            self.avoid.clone_from(&avoid);
+            self.solve_weighted(Limits::default(), weight)?;
+
+            let cost = self.get_path_cost()?;
+            if best.as_ref().is_none_or(|(best_cost, _)| cost < *best_cost) {
+                best = self.path.take().map(|path| (cost, path));
+            }
+            if weight == 1.0 {
+                break;
+            }
+        }
+
+        self.path = best.map(|(_, path)| path);
+        Ok(())
+    }
+
+    /// Solves the maze once with each of `algorithms` and returns statistics of every run, in the same order.
+    ///
+    /// Every run starts from the same state, fields set with `set_avoid` are avoided by all of them.
+    /// Path found by the last run is stored.
+    ///
+    /// # Errors
+    /// Same as `try_solve`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Algorithm, Maze};
+    ///
+    /// let mut maze = Maze::from_lines(&[
+    ///     "S.........",
+    ///     ".WWWWWWW..",
+    ///     ".......W..",
+    ///     "WWWWWW.W..",
+    ///     "......W..E",
+    /// ])
+    /// .unwrap();
+    ///
+    /// let stats = maze
+    ///     .bench_algorithms(&[Algorithm::AStar, Algorithm::Dijkstra])
+    ///     .unwrap();
+    /// assert_eq!(stats[0].0, Algorithm::AStar);
+    /// assert!(stats[1].1.expanded >= stats[0].1.expanded);
+    /// ```
+    pub fn bench_algorithms(
+        &mut self,
+        algorithms: &[Algorithm],
+    ) -> Result<Vec<(Algorithm, SolveStats)>> {
+        let avoid = self.avoid.clone();
+        let mut stats = vec![];
+
+        for &algorithm in algorithms {
+            self.avoid.clone_from(&avoid);
+            self.solve_weighted(Limits::default(), algorithm.weight())?;
+            stats.push((algorithm, self.stats.unwrap_or_default()));
+        }
+        Ok(stats)
+    }
+
+    /// Sets whether paths found by `solve_between` are cached by their `(start, end)`.
+    ///
+    /// Cache is cleared whenever the maze is edited or its costs change.
+    pub fn enable_path_cache(mut self, enabled: bool) -> Self {
+        self.path_cache = enabled.then(HashMap::new);
+        self
+    }
+
+    /// Removes every path cached by `solve_between`.
+    pub fn clear_path_cache(&mut self) {
+        if let Some(cache) = &mut self.path_cache {
+            cache.clear();
+        }
+    }
+
+    /// Returns the shortest path from `start` to `end` (`(x, y)`), regardless of the `start`/`end` markers.
+    ///
+    /// Stored path is left untouched. If path cache is enabled, repeated queries are answered without searching.
+    ///
+    /// # Errors
+    /// If either position is outside of the maze.
+    ///
+    /// If `end` can't be reached from `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["....", ".WW.", "...."])
+    ///     .unwrap()
+    ///     .enable_path_cache(true);
+    ///
+    /// let path = maze.solve_between((0, 1), (3, 1)).unwrap();
+    /// assert!(maze.solve_stats().unwrap().expanded > 0);
+    ///
+    /// assert_eq!(maze.solve_between((0, 1), (3, 1)).unwrap(), path);
+    /// assert_eq!(maze.solve_stats().unwrap().expanded, 0);
+    ///
+    /// // Editing the maze clears the cache.
+    /// maze.set_cell((1, 0), 'W').unwrap();
+    /// maze.solve_between((0, 1), (3, 1)).unwrap();
+    /// assert!(maze.solve_stats().unwrap().expanded > 0);
+    /// ```
+    pub fn solve_between(
+        &mut self,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Result<Vec<(usize, usize)>> {
+        if let Some(path) = self
+            .path_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&(start, end)))
+        {
+            self.stats = Some(SolveStats::default());
+            return Ok(path.clone());
+        }
+
+        let (start, end) = (Position(start), Position(end));
+        if !self.in_bounds(start) || !self.in_bounds(end) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        let (goal, stats) = search::search(
+            self,
+            start,
+            |position| position == end,
+            |position| self.heuristic(position, end),
+            &Limits::default(),
+            &self.tiebreaks(Some((start, end))),
+        );
+        self.stats = Some(stats);
+        let path: Vec<Field> = goal.ok_or(Error::from(MazeIsNotSolvable))?.trace().into();
+
+        if let Some(cache) = &mut self.path_cache {
+            cache.insert((start.xy_usize(), end.xy_usize()), path.clone());
+        }
+        Ok(path)
+    }
+
+    /// Returns the shortest path from `Start` to the nearest field on the edge of the maze.
+    ///
+    /// Stored path is left untouched.
+    ///
+    /// # Errors
+    /// If `start` is not set or no edge field is reachable from it.
+    ///
+    /// If `start`, `end`, `separator` or `wall` share the same character.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let room = ["WWWWWWW", "W.....W", "W..S..W", "W.....W", "WWWW.WW"];
+    /// let maze = Maze::try_from(room.join("\n").as_str()).unwrap();
+    /// let path = maze.escape_path().unwrap();
+    /// assert_eq!(path.len(), 3);
+    /// assert_eq!(path.last(), Some(&(4, 4)));
+    ///
+    /// // Door is closed, there is no way out.
+    /// let room = ["WWWWWWW", "W.....W", "W..S..W", "W.....W", "WWWWWWW"];
+    /// assert!(Maze::try_from(room.join("\n").as_str()).unwrap().escape_path().is_err());
+    /// ```
+    pub fn escape_path(&self) -> Result<Vec<(usize, usize)>> {
+        let start = self.start.ok_or(Error::from(StartEndNotSet))?;
+        if self.are_chars_invalid() {
+            return Err(InvalidCharacters.into());
+        }
+
+        let (x_max, y_max) = (self.x_len() - 1, self.y_len() - 1);
+        let goal = self
+            .search(
+                start,
+                |Position((x, y))| x == 0 || y == 0 || x == x_max || y == y_max,
+                |position| {
+                    let (x, y) = position.xy_usize();
+                    let estimate = x.min(y).min(x_max - x).min(y_max - y) * 10;
+                    self.portal_heuristic(position, estimate, 10.0)
+                },
+            )
+            .ok_or(Error::from(MazeIsNotSolvable))?;
+
+        Ok(goal.trace().into())
+    }
+
+    /// Stores `path` (`(x, y)`) as the solved path, so it can be printed and queried without calling `try_solve`.
+    ///
+    /// Path doesn't have to be the shortest, but it has to be legal (see `validate_path`),
+    /// starting at `start` and ending at `end`.
+    ///
+    /// # Errors
+    /// If `start`/`end` are not set.
+    ///
+    /// If `path` is empty, contains an illegal step or doesn't connect `start` with `end`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    ///
+    /// maze.set_path(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]).unwrap();
+    /// assert_eq!(maze.to_string(), "SXX\nWWX\n..E\n");
+    ///
+    /// assert!(maze.set_path(vec![(0, 0), (1, 1), (2, 2)]).is_err());
+    /// ```
+    pub fn set_path(&mut self, path: Vec<(usize, usize)>) -> Result<()> {
+        let (Some(start), Some(end)) = (self.start, self.end) else {
+            return Err(StartEndNotSet.into());
+        };
+
+        self.validate_path(&path)?;
+        if path.first() != Some(&start.xy_usize()) || path.last() != Some(&end.xy_usize()) {
+            return Err(InvalidPath.into());
+        }
+
+        self.path = Some(Path {
+            fields: path.into(),
+        });
+        Ok(())
+    }
+
+    /// Returns [`Vec`] that represents the shortest path from `Start` to the `End`
+    ///
+    /// Coordinates are `(x, y)` (column, row) unless changed with `set_coordinate_order`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn get_path(&self) -> Result<Vec<(usize, usize)>> {
+        let path = self.path_fields()?;
+
+        Ok(match self.order {
+            Order::ColRow => path,
+            Order::RowCol => path.into_iter().map(|(x, y)| (y, x)).collect(),
+        })
+    }
+
+    /// Same as `get_path`, but the path leads from `End` back to the `Start`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = maze.get_path_reversed().unwrap();
+    /// assert_eq!(path.first(), Some(&(2, 2)));
+    /// assert_eq!(path.last(), Some(&(0, 0)));
+    /// ```
+    pub fn get_path_reversed(&self) -> Result<Vec<(usize, usize)>> {
+        let mut path = self.get_path()?;
+        path.reverse();
+        Ok(path)
+    }
+
+    /// Returns the shortest path reflected across the vertical center axis of the maze,
+    /// `x` becomes `max_row_width - 1 - x`.
+    ///
+    /// Useful for checking that a mirrored maze is solved by the mirror image of the path.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S...", "WWW.", "E..."]).unwrap();
+    /// let mut reflected = Maze::from_lines(&["...S", ".WWW", "...E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// reflected.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap(), reflected.mirrored_path().unwrap());
+    /// ```
+    pub fn mirrored_path(&self) -> Result<Vec<(usize, usize)>> {
+        let width = self.max_row_width();
+        let path = self.path_fields()?;
+
+        Ok(path.into_iter().map(|(x, y)| (width - 1 - x, y)).collect())
+    }
+
+    /// Returns total cost of the shortest path.
+    ///
+    /// Costs are `1.0` and `√2` per step if float costs are set, `10` and `14` otherwise.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn get_path_cost(&self) -> Result<f64> {
+        let path = self.path_fields()?;
+
+        Ok(path
+            .windows(2)
+            .map(|step| {
+                let (from, to) = (Position(step[0]), Position(step[1]));
+                if self.float_costs {
+                    self.float_move_cost(from, to)
+                } else {
+                    self.move_cost(from, to) as f64
+                }
+            })
+            .sum())
+    }
+
+    /// Returns cost of the shortest path divided by the straight line distance between its start and end.
+    ///
+    /// Stretch of `1.0` means a direct route, the more the path has to detour (or pay for terrain), the higher it is.
+    /// Path that starts and ends on the same field has stretch of `1.0`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut corridor = Maze::from_lines(&["S....E"]).unwrap();
+    /// corridor.try_solve().unwrap();
+    /// assert_eq!(corridor.stretch().unwrap(), 1.0);
+    ///
+    /// let mut detour = Maze::from_lines(&["S.W.E", "..W..", "....."]).unwrap();
+    /// detour.try_solve().unwrap();
+    /// assert!(detour.stretch().unwrap() > 1.0);
+    /// ```
+    pub fn stretch(&self) -> Result<f64> {
+        let path = self.path_fields()?;
+        let cost = self.get_path_cost()?;
+
+        let (start, end) = (path[0], path[path.len() - 1]);
+        let distance = (end.0 as f64 - start.0 as f64).hypot(end.1 as f64 - start.1 as f64);
+        if distance == 0.0 {
+            return Ok(1.0);
+        }
+
+        let step = if self.float_costs { 1.0 } else { 10.0 };
+        Ok(cost / (distance * step))
+    }
+
+    /// Returns position `(x, y)` a fraction `t` of the way along the shortest path, measured by distance travelled.
+    ///
+    /// Position is interpolated between fields, `t` is clamped to `0.0..=1.0`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S...E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.path_at(0.0).unwrap(), (0.0, 0.0));
+    /// assert_eq!(maze.path_at(0.3).unwrap(), (1.2, 0.0));
+    /// assert_eq!(maze.path_at(1.0).unwrap(), (4.0, 0.0));
+    /// ```
+    pub fn path_at(&self, t: f64) -> Result<(f64, f64)> {
+        let path = self.path_fields()?;
+        let points: Vec<(f64, f64)> = path.iter().map(|&(x, y)| (x as f64, y as f64)).collect();
+        let lengths: Vec<f64> = points
+            .windows(2)
+            .map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1))
+            .collect();
+
+        let mut remaining = t.clamp(0.0, 1.0) * lengths.iter().sum::<f64>();
+        for (pair, length) in points.windows(2).zip(lengths) {
+            if remaining <= length && length > 0.0 {
+                let fraction = remaining / length;
+                return Ok((
+                    pair[0].0 + (pair[1].0 - pair[0].0) * fraction,
+                    pair[0].1 + (pair[1].1 - pair[0].1) * fraction,
+                ));
+            }
+            remaining -= length;
+        }
+        Ok(points[points.len() - 1])
+    }
+
+    /// Returns position `(x, y)` halfway along the shortest path, same as `path_at(0.5)`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn path_midpoint(&self) -> Result<(f64, f64)> {
+        self.path_at(0.5)
+    }
+
+    /// Sets order of coordinates returned by `get_path`.
+    ///
+    /// Every other method of [`Maze`] takes and returns `(x, y)` coordinates, regardless of this setting.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, Order};
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WWE"]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (2, 1)]);
+    ///
+    /// let maze = maze.set_coordinate_order(Order::RowCol);
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (0, 1), (1, 2)]);
+    /// ```
+    pub fn set_coordinate_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns how many steps of the shortest path go in each direction.
+    ///
+    /// Array is indexed by [`Direction::index`].
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze};
+    ///
+    /// let mut maze = Maze::try_from("S..\nWW.\n..E").unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let histogram = maze.direction_histogram().unwrap();
+    /// assert_eq!(histogram, [0, 0, 1, 1, 1, 0, 0, 0]);
+    /// assert_eq!(histogram[Direction::SouthEast.index()], 1);
+    /// ```
+    pub fn direction_histogram(&self) -> Result<[usize; 8]> {
+        let mut histogram = [0; 8];
+
+        for direction in self.path_directions()? {
+            histogram[direction.index()] += 1;
+        }
+
+        Ok(histogram)
+    }
+
+    /// Returns average change of direction between consecutive steps of the shortest path, in degrees.
+    ///
+    /// Straight path scores `0.0`, every 45 degree turn adds `45.0` before averaging.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::try_from("S...E").unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.smoothness().unwrap(), 0.0);
+    ///
+    /// // East, south-east and south, two 45 degree turns.
+    /// let mut maze = Maze::try_from("S..\nWW.\n..E").unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.smoothness().unwrap(), 45.0);
+    /// ```
+    pub fn smoothness(&self) -> Result<f64> {
+        let directions = self.path_directions()?;
+
+        if directions.len() < 2 {
+            return Ok(0.0);
+        }
+
+        let total = directions
+            .windows(2)
+            .map(|pair| pair[0].turn(pair[1]).unsigned_abs() * 45)
+            .sum::<usize>();
+
+        Ok(total as f64 / (directions.len() - 1) as f64)
+    }
+
+    /// Returns the shortest path compressed to its start, end and every field where direction changes.
+    ///
+    /// Fields in the middle of straight runs are dropped, line of sight between waypoints is not checked.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::try_from("S....\nWWWW.\nWWWW.\nWWWWE").unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap().len(), 7);
+    /// assert_eq!(maze.waypoints().unwrap(), vec![(0, 0), (3, 0), (4, 1), (4, 3)]);
+    /// ```
+    pub fn waypoints(&self) -> Result<Vec<(usize, usize)>> {
+        let path = self.path_fields()?;
+        let directions = self.path_directions()?;
+
+        let mut waypoints = vec![path[0]];
+        for (i, pair) in directions.windows(2).enumerate() {
+            if pair[0] != pair[1] {
+                waypoints.push(path[i + 1]);
+            }
+        }
+        if path.len() > 1 {
+            waypoints.push(path[path.len() - 1]);
+        }
+
+        Ok(waypoints)
+    }
+
+    /// Returns moves of the shortest path run-length encoded, each run is a direction and the number of
+    /// consecutive moves in it. Jumps through portals are left out.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze};
+    ///
+    /// let mut maze = Maze::from_lines(&[".....E", "......", "......", "S....."]).unwrap();
+    /// let mut path: Vec<_> = (0..6).map(|x| (x, 3)).collect();
+    /// path.extend([(5, 2), (5, 1), (5, 0)]);
+    /// maze.set_path(path).unwrap();
+    ///
+    /// assert_eq!(maze.path_rle().unwrap(), vec![(Direction::East, 5), (Direction::North, 3)]);
+    /// ```
+    pub fn path_rle(&self) -> Result<Vec<(Direction, usize)>> {
+        let mut runs: Vec<(Direction, usize)> = vec![];
+        for direction in self.path_directions()? {
+            match runs.last_mut() {
+                Some((last, count)) if *last == direction => *count += 1,
+                _ => runs.push((direction, 1)),
+            }
+        }
+
+        Ok(runs)
+    }
+
+    /// Returns the heuristic and the true remaining cost to the end of the path for each field of the shortest path,
+    /// as `(h_cost, remaining)` pairs from start to end.
+    ///
+    /// Remaining cost is computed with a backwards Dijkstra from the end, both are in integer units (`10` per straight step).
+    /// It is [`usize::MAX`] for fields the end can't be reached from, e.g. on a path stored with `set_path`.
+    /// Heuristic is admissible as long as `h_cost` never exceeds `remaining`, the closer they are the fewer nodes are expanded.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.W..", "..W..", ".....", "....E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let profile = maze.heuristic_profile().unwrap();
+    /// assert_eq!(profile.len(), maze.get_path().unwrap().len());
+    /// assert_eq!(profile.last(), Some(&(0, 0)));
+    /// assert!(profile.iter().all(|(h_cost, remaining)| h_cost <= remaining));
+    /// ```
+    pub fn heuristic_profile(&self) -> Result<Vec<(usize, usize)>> {
+        let path = self.path_fields()?;
+        let end = Position(path[path.len() - 1]);
+        let remaining = self.cost_field_to(end);
+
+        Ok(path
+            .into_iter()
+            .map(|field| {
+                let position = Position(field);
+                let cost = remaining.get(&position).copied().unwrap_or(usize::MAX);
+                (self.heuristic(position, end), cost)
+            })
+            .collect())
+    }
+
+    /// Returns fields of the shortest path grouped into runs of the same symbol, each run is the symbol
+    /// of its fields and their number. Start and end markers form runs of their own.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..11E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let runs = vec![('S', 1), ('.', 2), ('1', 2), ('E', 1)];
+    /// assert_eq!(maze.path_by_terrain().unwrap(), runs);
+    /// ```
+    pub fn path_by_terrain(&self) -> Result<Vec<(char, usize)>> {
+        let mut runs: Vec<(char, usize)> = vec![];
+        for (x, y) in self.path_fields()? {
+            let terrain = self.maze[y][x];
+            match runs.last_mut() {
+                Some((last, count)) if *last == terrain => *count += 1,
+                _ => runs.push((terrain, 1)),
+            }
+        }
+
+        Ok(runs)
+    }
+
+    /// Returns the shortest path simplified with the Ramer-Douglas-Peucker algorithm.
+    ///
+    /// Start and end are always kept, any other field is kept only if it is further than `epsilon` fields
+    /// from the line the simplified path follows around it. Unlike `waypoints`, small deviations are tolerated,
+    /// so a jagged but nearly straight path collapses to its ends. Line of sight between kept fields is not checked.
+    ///
+    /// Kept fields are usually not neighbours, so the stored path is left as it is.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&[".W.W.W.E", "S.W.W.W."]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// let path = maze.get_path().unwrap();
+    /// assert_eq!(maze.simplify_path(0.0).unwrap(), path);
+    ///
+    /// // Jagged path collapses to its ends.
+    /// assert_eq!(maze.simplify_path(1.5).unwrap(), vec![(0, 1), (7, 0)]);
+    /// assert_eq!(maze.get_path().unwrap(), path);
+    /// ```
+    pub fn simplify_path(&self, epsilon: f64) -> Result<Vec<(usize, usize)>> {
+        let path = self.path_fields()?;
+        if path.len() < 3 {
+            return Ok(path);
+        }
+
+        let mut keep = vec![false; path.len()];
+        let (first, last) = (0, path.len() - 1);
+        keep[first] = true;
+        keep[last] = true;
+
+        let mut segments = vec![(first, last)];
+        while let Some((from, to)) = segments.pop() {
+            let farthest = (from + 1..to)
+                .map(|i| (i, Maze::segment_distance(path[i], path[from], path[to])))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((i, distance)) = farthest {
+                if distance > epsilon {
+                    keep[i] = true;
+                    segments.push((from, i));
+                    segments.push((i, to));
+                }
+            }
+        }
+
+        Ok(path
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(field, keep)| keep.then_some(field))
+            .collect())
+    }
+
+    /// Returns the shortest path as turn and forward commands, for a robot starting with `initial` heading.
+    ///
+    /// Turns are multiples of 45 degrees in range `-135..=180`, a diagonal `Forward` moves onto the diagonal neighbour.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Direction, Maze, TurtleCmd};
+    ///
+    /// let mut maze = Maze::from_lines(&["S....", "WWWW.", "WWWW.", "WWWWE"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let forward = [TurtleCmd::Forward; 3];
+    /// let mut expected = forward.to_vec();
+    /// expected.extend([TurtleCmd::Turn(45), TurtleCmd::Forward, TurtleCmd::Turn(45)]);
+    /// expected.extend(&forward[..2]);
+    ///
+    /// assert_eq!(maze.get_turtle_commands(Direction::East).unwrap(), expected);
+    /// ```
+    pub fn get_turtle_commands(&self, initial: Direction) -> Result<Vec<TurtleCmd>> {
+        let mut heading = initial;
+        let mut commands = vec![];
+
+        for direction in self.path_directions()? {
+            if direction != heading {
+                commands.push(TurtleCmd::Turn(heading.turn(direction) * 45));
+                heading = direction;
+            }
+            commands.push(TurtleCmd::Forward);
+        }
+
+        Ok(commands)
+    }
+
+    /// Returns `false` if any field appears more than once along the stored path.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "...", "..E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.path_is_simple().unwrap());
+    /// ```
+    pub fn path_is_simple(&self) -> Result<bool> {
+        let path = self.path_fields()?;
+        let mut seen = HashSet::with_capacity(path.len());
+
+        Ok(path.into_iter().all(|field| seen.insert(field)))
+    }
+
+    /// Returns number of fields, moves by kind and the total cost of the shortest path.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Maze, PathMetrics};
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let metrics = PathMetrics {
+    ///     cells: 4,
+    ///     moves: 3,
+    ///     straight: 2,
+    ///     diagonal: 1,
+    ///     cost: 34,
+    /// };
+    /// assert_eq!(maze.path_metrics().unwrap(), metrics);
+    /// ```
+    pub fn path_metrics(&self) -> Result<PathMetrics> {
+        let path = self.path_fields()?;
+        let directions = self.path_directions()?;
+        let diagonal = directions
+            .iter()
+            .filter(|direction| direction.is_diagonal())
+            .count();
+        let cost = path
+            .windows(2)
+            .map(|step| self.move_cost(Position(step[0]), Position(step[1])))
+            .sum();
+
+        let markers = match self.marker_mode {
+            MarkerMode::Overlay => 0,
+            MarkerMode::Distinct => 2,
+        };
+
+        Ok(PathMetrics {
+            cells: path.len().saturating_sub(markers),
+            moves: path.len() - 1,
+            straight: directions.len() - diagonal,
+            diagonal,
+            cost,
+        })
+    }
+
+    /// Returns elevation of each field along the shortest path, from start to end.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// If elevation is not set, see `set_elevation`.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.E", "..."])
+    ///     .unwrap()
+    ///     .set_elevation(vec![vec![1, 9, 3], vec![2, 2, 2]])
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 1), (2, 0)]);
+    /// assert_eq!(maze.path_elevation_profile().unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn path_elevation_profile(&self) -> Result<Vec<i32>> {
+        let path = self.path_fields()?;
+        let elevation = self
+            .elevation
+            .as_ref()
+            .ok_or(Error::from(ElevationNotSet))?;
+
+        Ok(path.into_iter().map(|(x, y)| elevation[y][x]).collect())
+    }
+
+    /// Returns a human readable report of the last solve: dimensions, endpoints, length and cost of the path,
+    /// number of expanded nodes and whether diagonal moves or terrain costs were involved.
+    ///
+    /// Number of expanded nodes is taken from `solve_stats`, it is `unknown` if no search was run yet.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let report = maze.explain_solve().unwrap();
+    /// assert!(report.contains("Path length: 4 fields (3 moves)"));
+    /// assert!(report.contains("Cost: 34"));
+    /// assert!(report.contains("Diagonal moves: 1"));
+    /// assert!(report.contains("Terrain: none"));
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "..E"])
+    ///     .unwrap()
+    ///     .set_elevation(vec![vec![0; 3]; 3])
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.explain_solve().unwrap().contains("Terrain: elevation"));
+    /// ```
+    pub fn explain_solve(&self) -> Result<String> {
+        let path = self.path_fields()?;
+        let metrics = self.path_metrics()?;
+        let expanded = self
+            .stats
+            .map_or("unknown".to_string(), |stats| stats.expanded.to_string());
+
+        let mut terrain = vec![];
+        if self.costs.is_some() {
+            terrain.push("entry costs");
+        }
+        if self.elevation.is_some() {
+            terrain.push("elevation");
+        }
+        if !self.portals.is_empty() {
+            terrain.push("portals");
+        }
+        let terrain = if terrain.is_empty() {
+            "none".to_string()
+        } else {
+            terrain.join(", ")
+        };
+
+        let (width, height) = self.dimensions();
+        let mut report = format!("Dimensions: {}x{}\n", width, height);
+        report += &format!("Start: {:?}\n", path[0]);
+        report += &format!("End: {:?}\n", path[path.len() - 1]);
+        report += &format!(
+            "Path length: {} fields ({} moves)\n",
+            metrics.cells, metrics.moves
+        );
+        report += &format!("Cost: {}\n", self.get_path_cost()?);
+        report += &format!("Nodes expanded: {}\n", expanded);
+        report += &format!("Diagonal moves: {}\n", metrics.diagonal);
+        report += &format!("Terrain: {}\n", terrain);
+
+        Ok(report)
+    }
+
+    /// Returns Jaccard similarity of the fields of paths `a` and `b`, shared fields divided by all fields.
+    ///
+    /// Result is `1.0` for paths over the same fields and `0.0` for paths without a shared field,
+    /// two empty paths are treated as the same.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = [(0, 0), (1, 1), (2, 2)];
+    /// assert_eq!(Maze::path_overlap(&path, &path), 1.0);
+    /// assert_eq!(Maze::path_overlap(&path, &[(1, 0), (2, 1)]), 0.0);
+    /// assert_eq!(Maze::path_overlap(&path, &[(0, 0), (1, 0)]), 0.25);
+    /// ```
+    pub fn path_overlap(a: &[(usize, usize)], b: &[(usize, usize)]) -> f64 {
+        let a: HashSet<_> = a.iter().collect();
+        let b: HashSet<_> = b.iter().collect();
+
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 1.0;
+        }
+        a.intersection(&b).count() as f64 / union as f64
+    }
+
+    /// Returns the shortest path as CSV text, an `x,y` header followed by one row per field.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "##.", "E.."]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let csv = maze.path_to_csv().unwrap();
+    /// let mut rows = csv.lines();
+    /// assert_eq!(rows.next(), Some("x,y"));
+    /// assert_eq!(rows.count(), maze.get_path().unwrap().len());
+    /// ```
+    pub fn path_to_csv(&self) -> Result<String> {
+        let mut csv = String::from("x,y\n");
+        for (x, y) in self.path_fields()? {
+            csv.push_str(&format!("{x},{y}\n"));
+        }
+
+        Ok(csv)
+    }
+
+    /// Returns the shortest path as a GPX track with one `<trkpt>` per field.
+    ///
+    /// `origin` is the `(latitude, longitude)` of field `(0, 0)` and `scale` the number of degrees between
+    /// neighbouring fields. Columns grow towards east (longitude), rows towards south (latitude decreases).
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "...", "E.."]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// let gpx = maze.to_gpx((45.0, 15.0), 0.25).unwrap();
+    /// assert_eq!(gpx.matches("<trkpt ").count(), maze.get_path().unwrap().len());
+    /// assert!(gpx.contains(r#"<trkpt lat="44.75" lon="15"/>"#));
+    /// ```
+    pub fn to_gpx(&self, origin: (f64, f64), scale: f64) -> Result<String> {
+        let (latitude, longitude) = origin;
+
+        let mut gpx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        gpx.push_str("<gpx version=\"1.1\" creator=\"astar\">\n<trk>\n<trkseg>\n");
+        for (x, y) in self.path_fields()? {
+            let lat = latitude - y as f64 * scale;
+            let lon = longitude + x as f64 * scale;
+            gpx.push_str(&format!("<trkpt lat=\"{lat}\" lon=\"{lon}\"/>\n"));
+        }
+        gpx.push_str("</trkseg>\n</trk>\n</gpx>\n");
+
+        Ok(gpx)
+    }
+
+    /// Prints the solved [`Maze`], path is marked with `path_char`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn print_path(&self) -> Result<()> {
         if let Some(path) = &self.path {
-            let vec = path.fields.iter().copied().collect::<Vec<_>>();
-            Ok(vec)
+            print!("{}", self.draw(Some(path), self.full_window()));
+            Ok(())
+        } else {
+            Err(MazeNotSolved.into())
+        }
+    }
+
+    /// Returns the plain (uncoloured) grid with every field of the path labeled by its step index modulo `10`.
+    ///
+    /// Start is labeled `0`, markers are overwritten as well, so the order of traversal can be read from the grid.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "E.."]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.render_path_numbered().unwrap(), "01.\nWW2\n43.\n");
+    /// ```
+    pub fn render_path_numbered(&self) -> Result<String> {
+        let steps: HashMap<Field, usize> = self
+            .path_fields()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| (field, index))
+            .collect();
+
+        let mut out = String::new();
+        for (y, row) in self.maze.iter().enumerate() {
+            for (x, char) in row.iter().enumerate() {
+                match steps.get(&(x, y)) {
+                    Some(index) => out.extend(char::from_digit((index % 10) as u32, 10)),
+                    None => out.push(*char),
+                }
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Prints the solved [`Maze`] with path fields labeled by their step index, see `render_path_numbered`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn print_path_numbered(&self) -> Result<()> {
+        print!("{}", self.render_path_numbered()?);
+        Ok(())
+    }
+
+    /// Returns the smallest and the largest `(x, y)` coordinates of the path, corners of its bounding box.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn path_bounds(&self) -> Result<(Field, Field)> {
+        let path = self.path_fields()?;
+
+        let (min_x, max_x) = path.iter().fold((usize::MAX, 0), |(min, max), (x, _)| {
+            (min.min(*x), max.max(*x))
+        });
+        let (min_y, max_y) = path.iter().fold((usize::MAX, 0), |(min, max), (_, y)| {
+            (min.min(*y), max.max(*y))
+        });
+        Ok(((min_x, min_y), (max_x, max_y)))
+    }
+
+    /// Returns vertices of the convex hull of the path fields, treating each field as a point.
+    ///
+    /// Vertices start at the leftmost (then topmost) field and go clockwise as the maze is drawn (`y` grows downwards).
+    /// Fields lying on an edge of the hull are not included, a straight path has only its two ends.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..", "WW.", "E.."]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.get_path().unwrap(), vec![(0, 0), (1, 0), (2, 1), (1, 2), (0, 2)]);
+    /// assert_eq!(maze.path_hull().unwrap(), vec![(0, 0), (1, 0), (2, 1), (1, 2), (0, 2)]);
+    /// assert_eq!(maze.path_area().unwrap(), 3.0);
+    /// ```
+    pub fn path_hull(&self) -> Result<Vec<(usize, usize)>> {
+        let mut points = self.path_fields()?;
+        points.sort_unstable();
+        points.dedup();
+        if points.len() < 3 {
+            return Ok(points);
+        }
+
+        let cross = |o: Field, a: Field, b: Field| {
+            let (ax, ay) = (a.0 as isize - o.0 as isize, a.1 as isize - o.1 as isize);
+            let (bx, by) = (b.0 as isize - o.0 as isize, b.1 as isize - o.1 as isize);
+            ax * by - ay * bx
+        };
+
+        // Andrew's monotone chain, lower half of the hull followed by the upper one.
+        let mut hull: Vec<Field> = Vec::with_capacity(points.len() * 2);
+        for pass in [points.clone(), points.into_iter().rev().collect()] {
+            let floor = hull.len();
+            for point in pass {
+                while hull.len() >= floor + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+        }
+        Ok(hull)
+    }
+
+    /// Returns area enclosed by the convex hull of the path (see `path_hull`), fields are treated as points
+    /// one unit apart, so a straight path has area `0.0`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn path_area(&self) -> Result<f64> {
+        let hull = self.path_hull()?;
+
+        let twice_area: isize = (0..hull.len())
+            .map(|i| {
+                let ((x1, y1), (x2, y2)) = (hull[i], hull[(i + 1) % hull.len()]);
+                x1 as isize * y2 as isize - x2 as isize * y1 as isize
+            })
+            .sum();
+        Ok(twice_area.unsigned_abs() as f64 / 2.0)
+    }
+
+    /// Returns part of the solved [`Maze`] rendered the same way as `render`, cropped to the bounding box
+    /// of the path (see `path_bounds`) expanded by `margin` fields on each side.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["WWWWWWW", "W.....W", "W.S.E.W", "W.....W", "WWWWWWW"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// // Counts characters without colour codes.
+    /// let visible = |line: &str| {
+    ///     let mut chars = line.chars();
+    ///     let mut count = 0;
+    ///     while let Some(char) = chars.next() {
+    ///         match char {
+    ///             '\x1B' => _ = chars.find(|char| *char == 'm'),
+    ///             _ => count += 1,
+    ///         }
+    ///     }
+    ///     count
+    /// };
+    ///
+    /// let window = maze.render_path_window(1).unwrap();
+    /// let rows: Vec<&str> = window.lines().skip(1).collect();
+    /// // Path spans 3 x 1 fields, a margin of 1 adds a field on each side.
+    /// assert_eq!(rows.len(), 3);
+    /// // Each row is followed by a space and the vertical ruler.
+    /// assert!(rows.iter().all(|row| visible(row) == 5 + 2));
+    /// ```
+    pub fn render_path_window(&self, margin: usize) -> Result<String> {
+        let ((min_x, min_y), (max_x, max_y)) = self.path_bounds()?;
+        let width = self.max_row_width();
+
+        let columns = min_x.saturating_sub(margin)..(max_x + margin + 1).min(width);
+        let rows = min_y.saturating_sub(margin)..(max_y + margin + 1).min(self.y_len());
+        Ok(self.draw(self.path.as_ref(), (columns, rows)))
+    }
+
+    /// Prints the solved [`Maze`] cropped to the path and `margin` fields around it, see `render_path_window`.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    pub fn print_path_window(&self, margin: usize) -> Result<()> {
+        print!("{}", self.render_path_window(margin)?);
+        Ok(())
+    }
+
+    /// Prints the parsed [`Maze`].
+    ///
+    /// # Errors
+    /// If [`Maze`] is not set.
+    pub fn print_maze(&self) -> Result<()> {
+        if !self.maze.is_empty() {
+            print!("{}", self.draw(None, self.full_window()));
+            println!("\n\n");
+            Ok(())
+        } else {
+            Err(MazeIsNotSet.into())
+        }
+    }
+
+    /// Returns the [`Maze`] rendered the same way it is printed, path is included if the maze is solved.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not set.
+    pub fn render(&self) -> Result<String> {
+        if !self.maze.is_empty() {
+            Ok(self.draw(self.path.as_ref(), self.full_window()))
+        } else {
+            Err(MazeIsNotSet.into())
+        }
+    }
+
+    /// Returns the [`Maze`] rendered with half-block characters (`▀`, `▄`, `█`), two rows of fields per line.
+    ///
+    /// Walls are drawn as filled blocks in the wall colour and path fields (start and end included) in the path colour,
+    /// other fields are left blank. Path is included if the maze is solved.
+    ///
+    /// # Errors
+    /// If [`Maze`] is not set.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.W", "W..", "..W", "W.E", "..."]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.to_block_string().unwrap().lines().count(), 3);
+    /// ```
+    pub fn to_block_string(&self) -> Result<String> {
+        if self.maze.is_empty() {
+            return Err(MazeIsNotSet.into());
+        }
+
+        let path: HashSet<Field> = match &self.path {
+            Some(path) => path.fields.iter().copied().collect(),
+            None => HashSet::new(),
+        };
+        let colours = |(x, y): Field| match self.maze.get(y).and_then(|row| row.get(x)) {
+            Some(char) if *char == self.wall_char => Some((WALL_COLOUR, WALL_BACKGROUND)),
+            Some(_) if path.contains(&(x, y)) => Some((PATH_COLOUR, PATH_BACKGROUND)),
+            _ => None,
+        };
+
+        let mut out = String::new();
+        for y in (0..self.y_len()).step_by(2) {
+            for x in 0..self.max_row_width() {
+                let block = match (colours((x, y)), colours((x, y + 1))) {
+                    (None, None) => String::from(" "),
+                    (Some(top), Some(bottom)) if top == bottom => format!("{}█{RESET}", top.0),
+                    (Some(top), Some(bottom)) => format!("{}{}▀{RESET}", top.0, bottom.1),
+                    (Some(top), None) => format!("{}▀{RESET}", top.0),
+                    (None, Some(bottom)) => format!("{}▄{RESET}", bottom.0),
+                };
+                out.push_str(&block);
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Sets whether printed and rendered maze is followed by a legend explaining the symbols.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let path = std::env::temp_dir().join("astar_legend.txt");
+    /// std::fs::write(&path, "a..\n##.\n..b").unwrap();
+    ///
+    /// let mut maze = Maze::new()
+    ///     .set_start_char('a')
+    ///     .set_end_char('b')
+    ///     .set_walls_char('#')
+    ///     .set_path_char('*')
+    ///     .set(path.to_str().unwrap())
+    ///     .unwrap();
+    /// maze.try_solve().unwrap();
+    /// let plain = maze.render().unwrap();
+    ///
+    /// let maze = maze.set_show_legend(true);
+    /// let rendered = maze.render().unwrap();
+    /// assert!(rendered.starts_with(&plain));
+    /// assert_eq!(rendered.lines().count(), plain.lines().count() + 1);
+    ///
+    /// // Legend lists the configured symbols.
+    /// let legend = rendered.lines().last().unwrap();
+    /// assert!(['a', 'b', '#', '*'].iter().all(|symbol| legend.contains(*symbol)));
+    /// assert!(["start", "end", "wall", "path"].iter().all(|word| legend.contains(word)));
+    /// ```
+    pub fn set_show_legend(mut self, show: bool) -> Self {
+        self.show_legend = show;
+        self
+    }
+
+    /// Sets whether path is drawn over `start` and `end` markers that lie on it.
+    ///
+    /// By default markers take precedence, so start, end and any duplicate markers the
+    /// path passes through keep their symbol. Ignored if markers are [`MarkerMode::Distinct`].
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::try_from("S..\nWW.\n..E").unwrap();
+    /// maze.try_solve().unwrap();
+    /// let render = maze.render().unwrap();
+    /// assert_eq!(render.matches('X').count(), 2);
+    /// assert!(render.contains('S') && render.contains('E'));
+    ///
+    /// let render = maze.set_allow_start_end_overlap_with_path(true).render().unwrap();
+    /// assert_eq!(render.matches('X').count(), 4);
+    /// assert!(!render.contains('S') && !render.contains('E'));
+    /// ```
+    pub fn set_allow_start_end_overlap_with_path(mut self, allow: bool) -> Self {
+        self.path_over_markers = allow;
+        self
+    }
+
+    /// Sets whether `start` and `end` markers lie on open floor or are fields of their own, [`MarkerMode::Overlay`] by default.
+    ///
+    /// With [`MarkerMode::Distinct`] markers are never drawn over by the path and
+    /// `path_metrics` doesn't count start and end as path cells. Search is the same in both modes.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{MarkerMode, Maze};
+    ///
+    /// let solve = |mode| {
+    ///     let mut maze = Maze::from_lines(&["S..E"])
+    ///         .unwrap()
+    ///         .set_allow_start_end_overlap_with_path(true)
+    ///         .set_marker_mode(mode);
+    ///     maze.try_solve().unwrap();
+    ///     (maze.to_string(), maze.path_metrics().unwrap().cells)
+    /// };
+    ///
+    /// assert_eq!(solve(MarkerMode::Overlay), (String::from("XXXX\n"), 4));
+    /// assert_eq!(solve(MarkerMode::Distinct), (String::from("SXXE\n"), 2));
+    /// ```
+    pub fn set_marker_mode(mut self, mode: MarkerMode) -> Self {
+        self.marker_mode = mode;
+        self
+    }
+
+    /// Sets whether path is drawn with arrows (`→`, `↘`, ...) pointing to the next field instead of `path_char`.
+    ///
+    /// Last field of the path has no next field, it keeps `path_char` if drawn.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.E"]).unwrap().set_arrow_path(true);
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.to_string(), "S→E\n");
+    /// ```
+    pub fn set_arrow_path(mut self, arrows: bool) -> Self {
+        self.arrow_path = arrows;
+        self
+    }
+
+    /// Returns the symbol the path is drawn with.
+    ///
+    /// This is `path_char`, unless some field of the maze already holds it and the path would blend in.
+    /// In that case the first symbol of `*`, `+`, `o`, `@`, `#`, `%`, `&`, `~` that isn't used by the maze
+    /// (or any of its symbols) is drawn instead.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["SXXE"]).unwrap();
+    /// maze.try_solve().unwrap();
+    ///
+    /// assert_eq!(maze.path_char(), 'X');
+    /// assert_eq!(maze.drawn_path_char(), '*');
+    /// assert_eq!(maze.to_string(), "S**E\n");
+    /// ```
+    pub fn drawn_path_char(&self) -> char {
+        let used = |symbol: char| self.maze.iter().flatten().any(|field| *field == symbol);
+        if !used(self.path_char) {
+            return self.path_char;
+        }
+
+        let reserved = [
+            self.start_char,
+            self.end_char,
+            self.wall_char,
+            self.separator,
+        ];
+        PATH_CHAR_FALLBACKS
+            .into_iter()
+            .find(|symbol| !reserved.contains(symbol) && !used(*symbol))
+            .unwrap_or(self.path_char)
+    }
+
+    /// Writes `path_char` into every field of the shortest path except its first and last one,
+    /// so the grid returned by `field` holds the solved layout.
+    ///
+    /// This is destructive, overwritten fields (and their entry costs) are lost. Stored path is kept,
+    /// but it is drawn with a fallback symbol from now on (see `drawn_path_char`).
+    ///
+    /// # Errors
+    /// If [`Maze`] is not solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S..E", "WWWW"]).unwrap();
+    /// maze.try_solve().unwrap();
+    /// maze.apply_path_to_grid().unwrap();
+    ///
+    /// assert_eq!(maze.field()[0], vec!['S', 'X', 'X', 'E']);
+    /// ```
+    pub fn apply_path_to_grid(&mut self) -> Result<()> {
+        let path = self.path_fields()?;
+
+        for &(x, y) in path.iter().skip(1).take(path.len().saturating_sub(2)) {
+            self.maze[y][x] = self.path_char;
+        }
+        self.clear_path_cache();
+        Ok(())
+    }
+
+    /// Helper function returning the symbol drawn for the field at `(x, y)` and its colour.
+    ///
+    /// `path_char` is the symbol returned by `drawn_path_char`, passed in so it isn't searched for every field.
+    fn glyph(
+        &self,
+        (x, y): Field,
+        path: Option<&Path>,
+        path_char: char,
+    ) -> (char, Option<&'static str>) {
+        let char = self.maze[y][x];
+        let index = path.and_then(|path| path.fields.iter().position(|field| *field == (x, y)));
+        let on_path = index.is_some();
+        let path_char = match (path, index) {
+            (Some(path), Some(index)) if self.arrow_path => path
+                .fields
+                .get(index + 1)
+                .and_then(|&(nx, ny)| {
+                    Direction::from_offset((nx as isize - x as isize, ny as isize - y as isize))
+                })
+                .map_or(path_char, |direction| direction.arrow()),
+            _ => path_char,
+        };
+
+        let over_markers = self.path_over_markers && self.marker_mode == MarkerMode::Overlay;
+        if on_path && over_markers && char != self.wall_char {
+            (path_char, Some(PATH_COLOUR))
+        } else if char == self.wall_char {
+            (char, Some(WALL_COLOUR))
+        } else if char == self.start_char {
+            (char, Some(START_COLOUR))
+        } else if char == self.end_char {
+            (char, Some(END_COLOUR))
+        } else if on_path {
+            (path_char, Some(PATH_COLOUR))
+        } else {
+            (char, None)
+        }
+    }
+
+    /// Helper function returning columns and rows of the whole maze.
+    fn full_window(&self) -> (Range<usize>, Range<usize>) {
+        (0..self.max_row_width(), 0..self.y_len())
+    }
+
+    /// Helper function drawing `columns` and `rows` of the maze with their dimensions, overlaying `path` if there is one.
+    fn draw(&self, path: Option<&Path>, (columns, rows): (Range<usize>, Range<usize>)) -> String {
+        let x_str_len = columns.len().to_string().len() as i32;
+        let x_len = (columns.len() as i32 - x_str_len).unsigned_abs() as usize;
+
+        let y_str_len = rows.len().to_string().len() as i32;
+        let y_len = (rows.len() as i32 - y_str_len).unsigned_abs() as usize;
+
+        let horizontal = format!("<{:-^x_len$}>", columns.len());
+        let vertical: Vec<char> = format!("^{:|^y_len$}v", rows.len()).chars().collect();
+        let slice = &vertical[..];
+
+        let path_char = self.drawn_path_char();
+        let mut out = format!("{}\n", horizontal);
+        for (i, y) in rows.enumerate() {
+            for x in columns.clone().filter(|x| *x < self.maze[y].len()) {
+                match self.glyph((x, y), path, path_char) {
+                    (char, Some(colour)) => out.push_str(&format!("{}{char}{}", colour, RESET)),
+                    (char, None) => out.push(char),
+                }
+            }
+            out.push_str(&format!(" {}\n", slice[i]));
+        }
+
+        if self.show_legend {
+            out.push_str(&format!(
+                "{}{}{} start, {}{}{} end, {}{}{} wall, {}{}{} path\n",
+                START_COLOUR,
+                self.start_char,
+                RESET,
+                END_COLOUR,
+                self.end_char,
+                RESET,
+                WALL_COLOUR,
+                self.wall_char,
+                RESET,
+                PATH_COLOUR,
+                path_char,
+                RESET
+            ));
+        }
+        out
+    }
+
+    /// Helper function solving the maze from `start` to `end` within `limits` and storing the path.
+    fn solve_with(&mut self, limits: Limits<'_>) -> Result<()> {
+        self.solve_weighted(limits, 1.0)
+    }
+
+    /// Same as `solve_with`, heuristic is multiplied by `weight`.
+    ///
+    /// With `weight` above `1.0` the search is faster, but the found path can cost up to `weight` times the shortest one.
+    fn solve_weighted(&mut self, limits: Limits<'_>, weight: f64) -> Result<()> {
+        match self.run_search(limits, weight, None)? {
+            SolveProgress::Done(_) => Ok(()),
+            _ => Err(MazeIsNotSolvable.into()),
+        }
+    }
+
+    /// Helper function running (or continuing from `state`) the search from `start` to `end`,
+    /// the path is stored once it is found.
+    ///
+    /// Search pauses once `budget` of the `limits` runs out, the returned state has to be passed back
+    /// with the same `limits` and `weight` to continue.
+    fn run_search(
+        &mut self,
+        limits: Limits<'_>,
+        weight: f64,
+        state: Option<SolveState>,
+    ) -> Result<SolveProgress> {
+        let inflate = |estimate: usize| (estimate as f64 * weight) as usize;
+        let limits = Limits {
+            queue: if self.float_costs {
+                QueueImpl::Binary
+            } else {
+                self.queue
+            },
+            ..limits
+        };
+        self.path = None;
+        self.stats = None;
+
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if self.are_chars_invalid() {
+                return Err(InvalidCharacters.into());
+            }
+
+            let validate = self.validate_admissibility && state.is_none();
+            if validate && !self.is_heuristic_admissible(end) {
+                return Err(InadmissibleHeuristic.into());
+            }
+
+            let tiebreaks = self.tiebreaks(Some((start, end)));
+            let (outcome, stats) = if state.is_none() && self.is_single_step(start, end, &limits) {
+                let fields = VecDeque::from([start.xy_usize(), end.xy_usize()]);
+                (Outcome::Found(fields), SolveStats::default())
+            } else if self.resolution > 1 {
+                let grid = Upsampled {
+                    maze: self,
+                    factor: self.resolution,
+                };
+                let (fine_start, fine_end) = (grid.fine(start), grid.fine(end));
+                let on_expand = |position| {
+                    if let Some(on_expand) = limits.on_expand {
+                        on_expand(grid.coarse(position));
+                    }
+                };
+                let limits = Limits {
+                    on_expand: Some(&on_expand),
+                    ..limits
+                };
+                let heuristic = |position| inflate(grid.heuristic(position, end));
+                let state = state.unwrap_or_else(|| {
+                    SolveState::new(&grid, fine_start, heuristic(fine_start), &limits)
+                });
+                let (outcome, stats) = search::resume(
+                    &grid,
+                    state,
+                    |position| grid.coarse(position) == end,
+                    heuristic,
+                    &limits,
+                    &self.tiebreaks(Some((fine_start, fine_end))),
+                );
+                (outcome.map(|goal| grid.downsample(goal.trace())), stats)
+            } else if self.float_costs {
+                let limits = Limits {
+                    max_cost: limits
+                        .max_cost
+                        .map(|max_cost| (max_cost as f64 / 10.0 * FLOAT_SCALE) as usize),
+                    ..limits
+                };
+                let heuristic = |position| {
+                    let estimate = self.float_heuristic(position, end);
+                    inflate(self.portal_heuristic(position, estimate, FLOAT_SCALE))
+                };
+                let state = state.unwrap_or_else(|| {
+                    SolveState::new(&FloatCosts(self), start, heuristic(start), &limits)
+                });
+                let (outcome, stats) = search::resume(
+                    &FloatCosts(self),
+                    state,
+                    |position| position == end,
+                    heuristic,
+                    &limits,
+                    &tiebreaks,
+                );
+                (outcome.map(Node::trace), stats)
+            } else {
+                let heuristic = |position| inflate(self.heuristic(position, end));
+                let state = state
+                    .unwrap_or_else(|| SolveState::new(self, start, heuristic(start), &limits));
+                let (outcome, stats) = search::resume(
+                    self,
+                    state,
+                    |position| position == end,
+                    heuristic,
+                    &limits,
+                    &tiebreaks,
+                );
+                (outcome.map(Node::trace), stats)
+            };
+            self.stats = Some(stats);
+
+            if let Outcome::Paused(state) = outcome {
+                return Ok(SolveProgress::InProgress(state));
+            }
+            if !self.avoid.is_empty() {
+                self.avoid.clear();
+                self.clear_path_cache();
+            }
+            match outcome {
+                Outcome::Found(fields) => {
+                    let path = fields.iter().copied().collect();
+                    self.path = Some(Path { fields });
+                    Ok(SolveProgress::Done(path))
+                }
+                _ if limits.cancelled() => Err(Cancelled.into()),
+                _ => Ok(SolveProgress::Unsolvable),
+            }
+        } else {
+            Err(StartEndNotSet.into())
+        }
+    }
+
+    /// Helper function running A* from `start` until a position satisfying `is_goal` is reached.
+    ///
+    /// `heuristic` must not overestimate the cost to the nearest goal for the found path to be the shortest.
+    /// Returns the reached goal [`Node`], its path can be traced back through `previous`.
+    pub(crate) fn search<G, H>(&self, start: Position, is_goal: G, heuristic: H) -> Option<Node>
+    where
+        G: Fn(Position) -> bool,
+        H: Fn(Position) -> usize,
+    {
+        let tiebreaks = self.tiebreaks(None);
+        search::search(
+            self,
+            start,
+            is_goal,
+            heuristic,
+            &Limits::default(),
+            &tiebreaks,
+        )
+        .0
+    }
+
+    /// Helper function returning tie-breaks set for this maze, `line` is the `(start, goal)` of a single goal search.
+    fn tiebreaks(&self, line: Option<(Position, Position)>) -> Tiebreaks<'_> {
+        Tiebreaks {
+            cross: line.filter(|_| self.cross_tiebreak),
+            custom: self.tiebreak.as_deref(),
+        }
+    }
+
+    /// Helper function returning `true` if `end` is entered directly from `start` by the shortest path,
+    /// so the search can be skipped.
+    ///
+    /// A single step costs at most `14` plus entry and climbing costs of `end`, while any detour takes
+    /// at least two steps (`20`) and pays the same to enter `end`. A portal leaving `start` or leading
+    /// to `end` can make a detour cheaper, so such mazes are left to the search, along with subdivided fields,
+    /// custom costs, limits, cancellation and expansion callbacks.
+    fn is_single_step(&self, start: Position, end: Position, limits: &Limits) -> bool {
+        let touches_endpoint = |(a, b, _): &(Position, Position, usize)| {
+            [start, end].iter().any(|endpoint| *endpoint == *a || *endpoint == *b)
+        };
+
+        self.resolution == 1
+            && self.cost_fn.is_none()
+            && limits.max_cost.is_none()
+            && limits.max_steps.is_none()
+            && limits.on_expand.is_none()
+            && !limits.cancelled()
+            && !self.portals.iter().any(touches_endpoint)
+            && self.steps(start).contains(&end)
+    }
+
+    /// Helper function returning `true` if the heuristic never exceeds the cost of the shortest path to `end`,
+    /// checked for every field that can reach it.
+    fn is_heuristic_admissible(&self, end: Position) -> bool {
+        self.cost_field_to(end)
+            .into_iter()
+            .all(|(position, cost)| self.heuristic(position, end) <= cost)
+    }
+
+    /// Helper function dropping the stored path and every cached path, called whenever they may no longer be the shortest.
+    fn invalidate_paths(&mut self) {
+        self.path = None;
+        self.clear_path_cache();
+    }
+
+    /// Helper function returning `true` if `position` is inside of the maze.
+    pub(crate) fn in_bounds(&self, position: Position) -> bool {
+        let (x, y) = position.xy_usize();
+        y < self.maze.len() && x < self.maze[y].len()
+    }
+
+    /// Helper function returning `true` if the field at `position` can be entered.
+    pub(crate) fn is_walkable(&self, position: Position) -> bool {
+        let (x, y) = position.xy_usize();
+        self.in_bounds(position)
+            && self.maze[y][x] != self.wall_char
+            && self
+                .capacity_index(position)
+                .is_none_or(|index| self.capacities[index].1 > 0)
+            && !(self.solid_border && self.on_border(position) && !self.is_endpoint(position))
+            && !self.is_inflated(position)
+    }
+
+    /// Helper function returning `true` if a wall lies within `wall_inflation` fields (Chebyshev distance) of `position`,
+    /// endpoints are never closed by inflated walls.
+    fn is_inflated(&self, position: Position) -> bool {
+        let radius = self.wall_inflation;
+        if radius == 0 || self.is_endpoint(position) {
+            return false;
+        }
+
+        let (x, y) = position.xy_usize();
+        let rows = y.saturating_sub(radius)..(y + radius + 1).min(self.maze.len());
+        rows.into_iter().any(|y| {
+            let row = &self.maze[y];
+            let columns = x.saturating_sub(radius)..(x + radius + 1).min(row.len());
+            row[columns].contains(&self.wall_char)
+        })
+    }
+
+    /// Helper function returning walkable fields next to `position`, diagonal ones only if `diagonal`.
+    fn adjacent_fields(&self, position: Position, diagonal: bool) -> Vec<Field> {
+        if !self.in_bounds(position) {
+            return vec![];
+        }
+        let (x, y) = position.xy();
+
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| diagonal || !direction.is_diagonal())
+            .filter_map(|direction| {
+                let (offset_x, offset_y) = direction.offset();
+                let (x, y) = (x + offset_x, y + offset_y);
+                let neighbour = Position((usize::try_from(x).ok()?, usize::try_from(y).ok()?));
+                self.is_walkable(neighbour).then_some(neighbour.xy_usize())
+            })
+            .collect()
+    }
+
+    /// Helper function returning `true` if a thin wall separates `a` and `b`, see `set_wall_between`.
+    fn is_wall_between(&self, a: Position, b: Position) -> bool {
+        let (a, b) = (a.xy_usize(), b.xy_usize());
+        !self.thin_walls.is_empty() && self.thin_walls.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Helper function returning the field an endpoint requested at `position` is placed on, see `set_start`.
+    fn endpoint_field(&self, position: Field) -> Result<Field> {
+        if !self.in_bounds(Position(position)) {
+            return Err(PositionOutOfBounds.into());
+        }
+
+        if self.is_walkable(Position(position)) {
+            Ok(position)
+        } else if self.snap_endpoints {
+            let snapped = self.snap_to_open(position);
+            snapped.ok_or(Error::from(PositionNotWalkable))
+        } else {
+            Err(PositionNotWalkable.into())
+        }
+    }
+
+    /// Helper function returning `true` if `position` lies on the outermost ring of the maze.
+    fn on_border(&self, position: Position) -> bool {
+        let (x, y) = position.xy_usize();
+        x == 0 || y == 0 || y == self.maze.len() - 1 || x == self.maze[y].len() - 1
+    }
+
+    /// Helper function returning `true` if `position` is the `start` or the `end`.
+    fn is_endpoint(&self, position: Position) -> bool {
+        self.start == Some(position) || self.end == Some(position)
+    }
+
+    /// Helper function returning every walkable field that can be reached from `position` in a single step.
+    ///
+    /// These are the neighbouring fields and the other end of a portal if `position` is one.
+    pub(crate) fn steps(&self, position: Position) -> Vec<Position> {
+        let mut steps = self.connections(position);
+        steps.retain(|neighbour| !self.avoid.contains(neighbour));
+        steps
+    }
+
+    /// Same as `steps`, but fields set with `set_avoid` are included.
+    ///
+    /// Avoided fields only matter for the next solve, so connectivity is built from these.
+    pub(crate) fn connections(&self, position: Position) -> Vec<Position> {
+        let (x, y) = position.xy();
+
+        let directions = Direction::ALL
+            .into_iter()
+            .filter(|direction| self.movement.allows(*direction));
+        let neighbours = directions.filter_map(|direction| {
+            let (offset_x, offset_y) = direction.offset();
+            let (x, y) = (x + offset_x, y + offset_y);
+
+            (x >= 0 && y >= 0).then_some(Position((x as usize, y as usize)))
+        });
+        let portals = self.portals.iter().filter_map(|&(a, b, _)| {
+            if position == a {
+                Some(b)
+            } else if position == b {
+                Some(a)
+            } else {
+                None
+            }
+        });
+
+        let mut steps: Vec<Position> = vec![];
+        for neighbour in neighbours.chain(portals) {
+            if self.is_walkable(neighbour)
+                && !self.is_wall_between(position, neighbour)
+                && !self.is_step_blocked(position, neighbour)
+                && !steps.contains(&neighbour)
+            {
+                steps.push(neighbour);
+            }
+        }
+        steps
+    }
+
+    /// Helper function returning `true` if the function set by `set_cost_fn` blocks the step from `from` to
+    /// the neighbouring `to`, steps through portals are never blocked.
+    fn is_step_blocked(&self, from: Position, to: Position) -> bool {
+        self.cost_fn.is_some()
+            && self.portal_index(from, to).is_none()
+            && self.custom_cost(from, to).is_none()
+    }
+
+    /// Helper function rebuilding connectivity of walkable fields from scratch.
+    fn update_connectivity(&mut self) {
+        self.connectivity = None;
+        self.connectivity = Some(Connectivity::new(self));
+    }
+
+    /// Helper function returning index of `position` inside of `capacities`, if its capacity is limited.
+    pub(crate) fn capacity_index(&self, position: Position) -> Option<usize> {
+        self.capacities
+            .iter()
+            .position(|(limited, _)| *limited == position)
+    }
+
+    /// Helper function returning index of the portal linking `a` and `b` inside of `portals`.
+    fn portal_index(&self, a: Position, b: Position) -> Option<usize> {
+        self.portals
+            .iter()
+            .position(|&(x, y, _)| (x, y) == (a, b) || (x, y) == (b, a))
+    }
+
+    /// Helper function returning a lower bound of the cost of any path from `position` that goes through a portal,
+    /// in units of a straight step. Returns [`None`] if there are no portals.
+    fn portal_bound(&self, position: Position) -> Option<f64> {
+        self.portals
+            .iter()
+            .flat_map(|&(a, b, cost)| [(a, cost), (b, cost)])
+            .map(|(entry, cost)| {
+                let (x, y) = (entry.x() - position.x(), entry.y() - position.y());
+                (x as f64).hypot(y as f64) + cost as f64 / 10.0
+            })
+            .min_by(f64::total_cmp)
+    }
+
+    /// Helper function lowering `estimate` of the cost from `position` so it doesn't overestimate paths through portals.
+    ///
+    /// `scale` is the cost of a straight step in units of `estimate`.
+    fn portal_heuristic(&self, position: Position, estimate: usize, scale: f64) -> usize {
+        if self.cost_fn.is_some() {
+            return 0;
+        }
+        match self.portal_bound(position) {
+            Some(bound) => estimate.min((bound * scale) as usize),
+            None => estimate,
+        }
+    }
+
+    /// Helper function returning the heuristic from `position` to `end`.
+    fn heuristic(&self, position: Position, end: Position) -> usize {
+        let estimate = Node::heuristic(position, end, self.heuristic);
+        self.portal_heuristic(position, estimate, 10.0)
+    }
+
+    /// Helper function returning the cost of a single step from `from` to the neighbouring `to`.
+    pub(crate) fn move_cost(&self, from: Position, to: Position) -> usize {
+        if let Some(index) = self.portal_index(from, to) {
+            return self.portals[index].2 + self.entry_cost(to);
+        }
+        if let Some(cost) = self.custom_cost(from, to) {
+            return cost;
+        }
+        Node::step_cost(from, to) + self.entry_cost(to) + self.climb_cost(from, to)
+    }
+
+    /// Helper function returning cost of the step from `from` to `to` set by `set_cost_fn`, if there is one.
+    fn custom_cost(&self, from: Position, to: Position) -> Option<usize> {
+        self.cost_fn
+            .as_ref()
+            .and_then(|cost_fn| cost_fn(from.xy_usize(), to.xy_usize()))
+    }
+
+    /// Helper function returning the float cost of a single step from `from` to the neighbouring `to`.
+    fn float_move_cost(&self, from: Position, to: Position) -> f64 {
+        if let Some(index) = self.portal_index(from, to) {
+            return (self.portals[index].2 + self.entry_cost(to)) as f64 / 10.0;
+        }
+        if let Some(cost) = self.custom_cost(from, to) {
+            return cost as f64 / 10.0;
+        }
+        let step = if Node::step_cost(from, to) == 10 {
+            1.0
         } else {
-            Err(MazeNotSolved.into())
+            std::f64::consts::SQRT_2
+        };
+        step + (self.entry_cost(to) + self.climb_cost(from, to)) as f64 / 10.0
+    }
+
+    /// Helper function returning distance of `point` from the line through `a` and `b`, or from `a` if they are equal.
+    fn segment_distance(point: Field, a: Field, b: Field) -> f64 {
+        let (px, py) = (point.0 as f64 - a.0 as f64, point.1 as f64 - a.1 as f64);
+        let (dx, dy) = (b.0 as f64 - a.0 as f64, b.1 as f64 - a.1 as f64);
+
+        let length = dx.hypot(dy);
+        if length == 0.0 {
+            px.hypot(py)
+        } else {
+            (px * dy - py * dx).abs() / length
         }
     }
 
-    /// Prints the solved [`Maze`], path is marked with `path_char`.
+    /// Helper function returning straight line distance between `position` and `end`, scaled for [`FloatCosts`].
+    fn float_heuristic(&self, position: Position, end: Position) -> usize {
+        let (x, y) = (end.x() - position.x(), end.y() - position.y());
+        (self.heuristic.distance((x as f64, y as f64)) * FLOAT_SCALE) as usize
+    }
+
+    /// Helper function running Dijkstra from `from`, returning cost of the shortest path to every reachable field.
+    pub(crate) fn cost_field(&self, from: Position) -> HashMap<Position, usize> {
+        self.dijkstra(from, false)
+    }
+
+    /// Helper function running Dijkstra backwards from `to`, returning cost of the shortest path
+    /// from every field that can reach it.
+    pub(crate) fn cost_field_to(&self, to: Position) -> HashMap<Position, usize> {
+        self.dijkstra(to, true)
+    }
+
+    /// Helper function running Dijkstra from `root`, moves are followed backwards if `reverse`.
     ///
-    /// # Errors
-    /// If [`Maze`] is not solved.
-    pub fn print_path(&self) -> Result<()> {
-        if self.path.is_some() {
-            let x_str_len = self.x_len().to_string().len() as i32;
-            let x_len = (self.x_len() as i32 - x_str_len).unsigned_abs() as usize;
-
-            let y_str_len = self.y_len().to_string().len() as i32;
-            let y_len = (self.y_len() as i32 - y_str_len).unsigned_abs() as usize;
-
-            let horizontal = format!("<{:-^x_len$}>", self.x_len());
-            let vertical: Vec<char> = format!("^{:|^y_len$}v", self.y_len()).chars().collect();
-            let slice = &vertical[..];
-
-            println!("{}", horizontal);
-            for (y, row) in self.maze.iter().enumerate() {
-                for (x, char) in row.iter().copied().enumerate() {
-                    if char == self.wall_char {
-                        print!("{}{char}{}", WALL_COLOUR, RESET)
-                    } else if char == self.start_char {
-                        print!("{}{char}{}", START_COLOUR, RESET)
-                    } else if char == self.end_char {
-                        print!("{}{char}{}", END_COLOUR, RESET)
-                    } else if self.path.as_ref().unwrap().fields.contains(&(x, y)) {
-                        print!("{}{}{}", PATH_COLOUR, self.path_char, RESET)
+    /// Fields are connected both ways, only cost of a move depends on its direction.
+    fn dijkstra(&self, root: Position, reverse: bool) -> HashMap<Position, usize> {
+        let mut costs = HashMap::from([(root, 0)]);
+        let mut open = BinaryHeap::from([Reverse((0, root.xy_usize()))]);
+
+        while let Some(Reverse((cost, position))) = open.pop() {
+            let position = Position(position);
+            if costs.get(&position).is_some_and(|best| *best < cost) {
+                continue;
+            }
+            for neighbour in self.steps(position) {
+                let cost = cost
+                    + if reverse {
+                        self.move_cost(neighbour, position)
                     } else {
-                        print!("{char}")
-                    }
+                        self.move_cost(position, neighbour)
+                    };
+
+                if costs.get(&neighbour).is_none_or(|best| cost < *best) {
+                    costs.insert(neighbour, cost);
+                    open.push(Reverse((cost, neighbour.xy_usize())));
                 }
-                println!(" {}", slice[y]);
             }
+        }
+        costs
+    }
 
-            Ok(())
-        } else {
-            Err(MazeIsNotSet.into())
+    /// Helper function returning every walkable field reachable from `from`.
+    pub(crate) fn flood_fill(&self, from: Position) -> HashSet<Position> {
+        let mut region = HashSet::new();
+        if !self.is_walkable(from) {
+            return region;
+        }
+
+        let mut queue = VecDeque::from([from]);
+        region.insert(from);
+        while let Some(position) = queue.pop_front() {
+            for neighbour in self.steps(position) {
+                if region.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
         }
+        region
     }
 
-    /// Prints the parsed [`Maze`].
-    ///
-    /// # Errors
-    /// If [`Maze`] is not set.
-    pub fn print_maze(&self) -> Result<()> {
-        if !self.maze.is_empty() {
-            let x_str_len = self.x_len().to_string().len() as i32;
-            let x_len = (self.x_len() as i32 - x_str_len).unsigned_abs() as usize;
-
-            let y_str_len = self.y_len().to_string().len() as i32;
-            let y_len = (self.y_len() as i32 - y_str_len).unsigned_abs() as usize;
-
-            let horizontal = format!("<{:-^x_len$}>", self.x_len());
-            let vertical: Vec<char> = format!("^{:|^y_len$}v", self.y_len()).chars().collect();
-            let slice = &vertical[..];
-
-            println!("{}", horizontal);
-            for (y, row) in self.maze.iter().enumerate() {
-                for char in row.iter().copied() {
-                    if char == self.wall_char {
-                        print!("{}{char}{}", WALL_COLOUR, RESET)
-                    } else if char == self.start_char {
-                        print!("{}{char}{}", START_COLOUR, RESET)
-                    } else if char == self.end_char {
-                        print!("{}{char}{}", END_COLOUR, RESET)
-                    } else {
-                        print!("{char}")
-                    }
+    /// Helper function iterating over all walkable fields, row by row.
+    pub(crate) fn walkable_fields(&self) -> impl Iterator<Item = Position> + '_ {
+        self.maze
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| (0..row.len()).map(move |x| Position((x, y))))
+            .filter(|position| self.is_walkable(*position))
+    }
+
+    /// Helper function returning the additional cost of entering `position`.
+    pub(crate) fn entry_cost(&self, position: Position) -> usize {
+        if self.endpoints_plain && self.is_endpoint(position) {
+            return 0;
+        }
+        let (x, y) = position.0;
+        match &self.costs {
+            Some(costs) => costs
+                .get(y)
+                .and_then(|row| row.get(x))
+                .map_or(0, |cost| cost * 10),
+            None => 0,
+        }
+    }
+
+    /// Helper function returning extra cost of climbing from `from` to `to`, `0` if not uphill.
+    pub(crate) fn climb_cost(&self, from: Position, to: Position) -> usize {
+        let plain = self.endpoints_plain && (self.is_endpoint(from) || self.is_endpoint(to));
+
+        match &self.elevation {
+            Some(elevation) if !plain => {
+                let height = |(x, y): Field| elevation.get(y).and_then(|row| row.get(x)).copied();
+                match (height(from.0), height(to.0)) {
+                    (Some(from), Some(to)) => (to - from).max(0) as usize * 10,
+                    _ => 0,
                 }
-                println!(" {}", slice[y]);
             }
-            println!("\n\n");
-            Ok(())
+            _ => 0,
+        }
+    }
+
+    /// Helper function returning the shortest path as `(x, y)` coordinates, regardless of `order`.
+    fn path_fields(&self) -> Result<Vec<Field>> {
+        if let Some(path) = &self.path {
+            Ok(path.fields.iter().copied().collect())
         } else {
-            Err(MazeIsNotSet.into())
+            Err(MazeNotSolved.into())
         }
     }
 
+    /// Helper function returning direction of each step along the shortest path.
+    fn path_directions(&self) -> Result<Vec<Direction>> {
+        let path = self.path_fields()?;
+
+        Ok(path
+            .windows(2)
+            .filter_map(|step| {
+                let offset = (
+                    step[1].0 as isize - step[0].0 as isize,
+                    step[1].1 as isize - step[0].1 as isize,
+                );
+                Direction::from_offset(offset)
+            })
+            .collect())
+    }
+
     /// Helper function for checking if all characters are unique.
     fn are_chars_invalid(&self) -> bool {
         self.end_char == self.start_char
@@ -394,8 +4732,105 @@ impl Maze {
             || self.wall_char == self.end_char
     }
 
+    /// Helper function reading the maze text file, stripping ANSI escape sequences and comment lines if enabled.
+    fn read_maze(&self, path: &str) -> std::io::Result<String> {
+        let mut maze = fs::read_to_string(path)?;
+
+        if let Some(prefix) = self.comment_prefix {
+            maze = maze
+                .lines()
+                .filter(|line| !line.trim_start().starts_with(prefix))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
+        if !self.strip_ansi {
+            return Ok(maze);
+        }
+
+        let mut stripped = String::with_capacity(maze.len());
+        let mut chars = maze.chars();
+        while let Some(char) = chars.next() {
+            if char != '\x1B' {
+                stripped.push(char);
+            } else if chars.next() == Some('[') {
+                // Control sequence, parameters run until the final byte in range `@`..=`~`.
+                for char in chars.by_ref() {
+                    if ('@'..='~').contains(&char) {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(stripped)
+    }
+
+    /// Helper function for setting an already parsed grid, validating that it is non-empty and rectangular.
+    fn set_grid(mut self, maze: Vec<Vec<char>>) -> Result<Self> {
+        if maze.is_empty()
+            || maze[0].is_empty()
+            || maze.iter().any(|row| row.len() != maze[0].len())
+        {
+            return Err(InvalidGrid.into());
+        }
+
+        self.replace_grid(maze)?;
+
+        Ok(self)
+    }
+
+    /// Helper function replacing the grid, entry costs and elevation aligned to the old one are dropped.
+    ///
+    /// Fails with [`InvalidCharacters`] if the separator is used as a field.
+    fn replace_grid(&mut self, maze: Vec<Vec<char>>) -> Result<()> {
+        if maze.iter().flatten().any(|char| *char == self.separator) {
+            return Err(InvalidCharacters.into());
+        }
+
+        self.maze = maze;
+        self.costs = None;
+        self.elevation = None;
+        self.invalidate_paths();
+        self.calculate_start();
+        self.calculate_end();
+        self.update_connectivity();
+        Ok(())
+    }
+
+    /// Helper function setting entry costs of the fields, `costs` must match the maze dimensions.
+    fn set_costs(mut self, costs: Vec<Vec<usize>>) -> Result<Self> {
+        if costs.len() != self.y_len()
+            || costs
+                .iter()
+                .zip(&self.maze)
+                .any(|(a, b)| a.len() != b.len())
+        {
+            return Err(InvalidCostFile.into());
+        }
+
+        self.costs = Some(costs);
+        self.invalidate_paths();
+
+        Ok(self)
+    }
+
+    /// Helper function returning positions of every field holding `symbol`.
+    fn find_all(&self, symbol: char) -> Vec<(usize, usize)> {
+        self.maze
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(move |(_, char)| **char == symbol)
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect()
+    }
+
     /// Helper function for finding start character and setting start position.
     fn calculate_start(&mut self) {
+        self.start = None;
         for (i, row) in self.maze.iter().enumerate() {
             let start = row
                 .iter()
@@ -410,6 +4845,7 @@ impl Maze {
 
     /// Helper function for finding end character and setting end position.
     fn calculate_end(&mut self) {
+        self.end = None;
         for (i, row) in self.maze.iter().enumerate() {
             let start = row
                 .iter()
@@ -423,8 +4859,411 @@ impl Maze {
     }
 }
 
+/// [`Maze`] searched with float costs, they are converted to fixed point with [`FLOAT_SCALE`].
+///
+/// Diagonal cost is rounded up, so the heuristic (rounded down) never overestimates.
+struct FloatCosts<'a>(&'a Maze);
+
+impl Grid for FloatCosts<'_> {
+    fn steps(&self, position: Position) -> Vec<Position> {
+        self.0.steps(position)
+    }
+
+    fn move_cost(&self, from: Position, to: Position) -> usize {
+        (self.0.float_move_cost(from, to) * FLOAT_SCALE).ceil() as usize
+    }
+
+    fn capacities(&self) -> &[(Position, usize)] {
+        &self.0.capacities
+    }
+
+    fn restricts_turns(&self) -> bool {
+        self.0.restricts_turns()
+    }
+
+    fn forbids_turn(&self, at: Position, from: Direction, to: Direction) -> bool {
+        self.0.forbids_turn(at, from, to)
+    }
+}
+
+/// [`Maze`] with every field subdivided into `factor` x `factor` fields, see [`Maze::set_resolution`].
+struct Upsampled<'a> {
+    maze: &'a Maze,
+    factor: usize,
+}
+
+impl Upsampled<'_> {
+    /// Returns the field of the original maze that contains `position`.
+    fn coarse(&self, position: Position) -> Position {
+        let (x, y) = position.xy_usize();
+        Position((x / self.factor, y / self.factor))
+    }
+
+    /// Returns the middle of the original field at `position`.
+    fn fine(&self, position: Position) -> Position {
+        let (x, y) = position.xy_usize();
+        let middle = self.factor / 2;
+        Position((x * self.factor + middle, y * self.factor + middle))
+    }
+
+    /// Returns heuristic from `position` to the closest field inside of the original `end`.
+    fn heuristic(&self, position: Position, end: Position) -> usize {
+        let clamp = |value: usize, field: usize| {
+            value.clamp(field * self.factor, field * self.factor + self.factor - 1)
+        };
+        let (x, y) = position.xy_usize();
+        let closest = Position((clamp(x, end.0 .0), clamp(y, end.0 .1)));
+        Node::heuristic(position, closest, self.maze.heuristic)
+    }
+
+    /// Maps `fields` back to the original maze, consecutive fields inside the same original one are merged.
+    fn downsample(&self, fields: VecDeque<Field>) -> VecDeque<Field> {
+        let mut path = VecDeque::with_capacity(fields.len() / self.factor + 1);
+        for field in fields {
+            let field = self.coarse(Position(field)).xy_usize();
+            if path.back() != Some(&field) {
+                path.push_back(field);
+            }
+        }
+        path
+    }
+}
+
+impl Grid for Upsampled<'_> {
+    fn steps(&self, position: Position) -> Vec<Position> {
+        let (x, y) = position.xy();
+
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| self.maze.movement.allows(*direction))
+            .filter_map(|direction| {
+                let (offset_x, offset_y) = direction.offset();
+                let (x, y) = (x + offset_x, y + offset_y);
+                if x < 0 || y < 0 {
+                    return None;
+                }
+
+                let neighbour = Position((x as usize, y as usize));
+                let field = self.coarse(neighbour);
+                let open = self.maze.is_walkable(field)
+                    && !self.maze.avoid.contains(&field)
+                    && !self.maze.is_wall_between(self.coarse(position), field);
+                open.then_some(neighbour)
+            })
+            .collect()
+    }
+
+    fn move_cost(&self, from: Position, to: Position) -> usize {
+        let (from_field, to_field) = (self.coarse(from), self.coarse(to));
+        let entered = if from_field != to_field {
+            self.maze.entry_cost(to_field) + self.maze.climb_cost(from_field, to_field)
+        } else {
+            0
+        };
+        Node::step_cost(from, to) + entered
+    }
+}
+
+impl Grid for Maze {
+    fn steps(&self, position: Position) -> Vec<Position> {
+        Maze::steps(self, position)
+    }
+
+    fn move_cost(&self, from: Position, to: Position) -> usize {
+        Maze::move_cost(self, from, to)
+    }
+
+    fn capacities(&self) -> &[(Position, usize)] {
+        &self.capacities
+    }
+
+    fn restricts_turns(&self) -> bool {
+        !self.turn_restrictions.is_empty()
+    }
+
+    fn forbids_turn(&self, at: Position, from: Direction, to: Direction) -> bool {
+        self.turn_restrictions.contains(&(at, from, to))
+    }
+}
+
 impl Default for Maze {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+impl Display for Maze {
+    /// Writes the plain (uncoloured) grid, path is overlaid with `drawn_path_char` if the maze is solved.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let mut maze = Maze::from_lines(&["S.W", "..E"]).unwrap().set_path_char('*');
+    /// assert!(!maze.to_string().contains('*'));
+    ///
+    /// maze.try_solve().unwrap();
+    /// assert!(maze.to_string().contains('*'));
+    /// assert!(!maze.to_string().contains('\x1B'));
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let path_char = self.drawn_path_char();
+        for (y, row) in self.maze.iter().enumerate() {
+            for x in 0..row.len() {
+                write!(f, "{}", self.glyph((x, y), self.path.as_ref(), path_char).0)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<Vec<Vec<char>>> for Maze {
+    type Error = Error;
+
+    /// Constructs a [`Maze`] with default symbols from an already parsed grid.
+    ///
+    /// # Errors
+    /// If the grid is empty, its rows are not the same length or the separator is used as a field.
+    fn try_from(value: Vec<Vec<char>>) -> Result<Self> {
+        Maze::new().set_grid(value)
+    }
+}
+
+/// Returns `rows` and `columns` of the `grid`, rows shorter than `columns` are cut where they end.
+fn crop<T: Clone>(grid: &[Vec<T>], rows: Range<usize>, columns: Range<usize>) -> Vec<Vec<T>> {
+    grid[rows]
+        .iter()
+        .map(|row| {
+            row.iter()
+                .skip(columns.start)
+                .take(columns.len())
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns `true` if `rows` join into rows of `width` fields when consecutive short ones are glued back
+/// together with a separator between them, meaning the separator was used as a field.
+///
+/// Empty rows before a short one are blank lines, after it they are left by a separator next to the stray one.
+fn is_split_row(rows: &[Vec<char>], width: usize) -> bool {
+    let mut joined = None;
+
+    for row in rows {
+        joined = match joined {
+            None if row.is_empty() => None,
+            None => Some(row.len()),
+            Some(len) => Some(len + 1 + row.len()),
+        };
+        match joined {
+            Some(len) if len == width => joined = None,
+            Some(len) if len > width => return false,
+            _ => {}
+        }
+    }
+    joined.is_none()
+}
+
+/// Splits text into rows of fields on newlines, empty lines are skipped.
+fn split_rows(text: &str) -> Vec<Vec<char>> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+/// Replaces empty rows between the first and the last non-empty one with rows of `fill` as wide as the widest row.
+fn fill_blank_rows(rows: Vec<Vec<char>>, fill: char) -> Vec<Vec<char>> {
+    let filled = |row: &Vec<char>| !row.is_empty();
+    let first = rows.iter().position(filled).unwrap_or(0);
+    let last = rows.iter().rposition(filled).map_or(0, |last| last + 1);
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    rows.into_iter()
+        .take(last)
+        .skip(first)
+        .map(|row| {
+            if row.is_empty() {
+                vec![fill; width]
+            } else {
+                row
+            }
+        })
+        .collect()
+}
+
+impl TryFrom<&str> for Maze {
+    type Error = Error;
+
+    /// Constructs a [`Maze`] with default symbols from text, rows are split on newlines the same way as in `set`.
+    ///
+    /// # Errors
+    /// If the grid is empty or its rows are not the same length.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::Maze;
+    ///
+    /// let text = "S..\nWW.\n..E\n";
+    /// let maze = Maze::try_from(text).unwrap();
+    /// assert_eq!(maze.field()[1], vec!['W', 'W', '.']);
+    ///
+    /// let grid = Maze::try_from(maze.field().to_vec()).unwrap();
+    /// assert_eq!(grid.field(), maze.field());
+    /// assert!(Maze::try_from("S..\n.E").is_err());
+    /// ```
+    fn try_from(value: &str) -> Result<Self> {
+        Maze::try_from(split_rows(value))
+    }
+}
+
+impl FromStr for Maze {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Maze::try_from(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maze of `width` x `height` fields with start in the top left and end in the bottom right corner,
+    /// any other field is a wall with probability `density`.
+    fn random_maze(width: usize, height: usize, density: f64, seed: u64) -> Maze {
+        let mut rng = Rng::new(seed);
+        let mut grid = vec![vec!['.'; width]; height];
+        for field in grid.iter_mut().flatten() {
+            if (rng.below(1000) as f64) < density * 1000.0 {
+                *field = 'W';
+            }
+        }
+        grid[0][0] = 'S';
+        grid[height - 1][width - 1] = 'E';
+        Maze::try_from(grid).unwrap()
+    }
+
+    /// Returns `true` if a flood fill over walkable fields starting at `start` reaches `end`.
+    fn flood_fill_reaches(maze: &Maze, start: Field, end: Field) -> bool {
+        let (width, height) = maze.dimensions();
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+
+        while let Some((x, y)) = stack.pop() {
+            if (x, y) == end {
+                return true;
+            }
+            for (dx, dy) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (dx, dy))) {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if nx < 0 || ny < 0 || nx as usize >= width || ny as usize >= height {
+                    continue;
+                }
+                let next = (nx as usize, ny as usize);
+                if maze.field()[next.1][next.0] != maze.wall() && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// Cost of the shortest path from `start` to `end` found by plain Dijkstra over every field.
+    fn brute_force_cost(maze: &Maze, start: Field, end: Field) -> Option<usize> {
+        let (width, height) = maze.dimensions();
+        let mut best = HashMap::from([(start, 0)]);
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((cost, (x, y)))) = queue.pop() {
+            if (x, y) == end {
+                return Some(cost);
+            }
+            if best[&(x, y)] < cost {
+                continue;
+            }
+            for (dx, dy) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (dx, dy))) {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if (dx, dy) == (0, 0) || nx < 0 || ny < 0 {
+                    continue;
+                }
+                let next = (nx as usize, ny as usize);
+                if next.0 >= width
+                    || next.1 >= height
+                    || maze.field()[next.1][next.0] == maze.wall()
+                {
+                    continue;
+                }
+                let cost = cost + if dx != 0 && dy != 0 { 14 } else { 10 };
+                if best.get(&next).is_none_or(|&known| cost < known) {
+                    best.insert(next, cost);
+                    queue.push(Reverse((cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Cost of walking `path`, `10` for a straight and `14` for a diagonal step.
+    fn walked_cost(path: &[Field]) -> usize {
+        path.windows(2)
+            .map(|step| {
+                let ((ax, ay), (bx, by)) = (step[0], step[1]);
+                if ax != bx && ay != by {
+                    14
+                } else {
+                    10
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn path_stepping_back_onto_a_field_is_not_simple() {
+        let mut maze = Maze::from_lines(&["S..", "...", "..E"]).unwrap();
+        maze.path = Some(Path {
+            fields: VecDeque::from([(0, 0), (1, 0), (1, 1), (1, 0), (2, 1), (2, 2)]),
+        });
+
+        assert!(!maze.path_is_simple().unwrap());
+    }
+
+    #[test]
+    fn path_cost_matches_brute_force() {
+        for seed in 0..16 {
+            let (width, height) = (5 + seed as usize % 7, 4 + seed as usize % 5);
+            let mut maze = random_maze(width, height, 0.3, seed);
+            let expected = brute_force_cost(&maze, (0, 0), (width - 1, height - 1));
+
+            let cost = maze
+                .try_solve()
+                .ok()
+                .map(|_| walked_cost(&maze.get_path().unwrap()));
+            assert_eq!(cost, expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn solvability_matches_flood_fill_after_toggling_cells() {
+        let mut maze = random_maze(9, 7, 0.35, 3);
+        let mut rng = Rng::new(11);
+        let end = (8, 6);
+
+        for step in 0..200 {
+            let (x, y) = (rng.below(9), rng.below(7));
+            if (x, y) == (0, 0) || (x, y) == end {
+                continue;
+            }
+            let symbol = if maze.field()[y][x] == maze.wall() {
+                '.'
+            } else {
+                maze.wall()
+            };
+            maze.set_cell((x, y), symbol).unwrap();
+
+            let expected = flood_fill_reaches(&maze, (0, 0), end);
+            assert_eq!(maze.is_solvable(), expected, "step {step}");
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::maze::Maze;
+use crate::direction::Direction;
+use crate::search::Grid;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::hash::{Hash, Hasher};
 
 /// Node represents each field in 2D maze, it contains `Position` and costs/weights.
@@ -11,87 +14,159 @@ pub(crate) struct Node {
     pub(crate) g_cost: usize,
     pub(crate) h_cost: usize,
     pub(crate) previous: Option<Box<Node>>,
+    /// Number of times each capacity limited field was entered along the path to this node.
+    pub(crate) visits: Vec<usize>,
+    /// Number of steps from the first node, only tracked when the search limits path length.
+    pub(crate) steps: Option<usize>,
+    /// Direction of the step onto this node, only tracked when the grid restricts turns.
+    pub(crate) heading: Option<Direction>,
 }
 
 impl Node {
-    fn new(position: Position, previous: &Node, end: Position) -> Self {
-        let mut node = Node {
+    pub(crate) fn start<T: Grid>(
+        position: Position,
+        grid: &T,
+        h_cost: usize,
+        track_steps: bool,
+    ) -> Self {
+        Node {
             position,
             g_cost: 0,
-            h_cost: 0,
+            h_cost,
             previous: None,
-        };
-
-        node.h_cost = Node::heuristic(node.position, end);
-        node.g_cost = Node::g_cost(position, previous);
-        node
+            visits: vec![0; grid.capacities().len()],
+            steps: track_steps.then_some(0),
+            heading: None,
+        }
     }
 
-    pub(crate) fn neighbours(&self, maze: &Maze) -> Vec<Node> {
-        let mut neighbours = vec![];
+    fn new<T: Grid>(
+        position: Position,
+        previous: &Node,
+        grid: &T,
+        h_cost: usize,
+        heading: Option<Direction>,
+    ) -> Self {
+        Node {
+            position,
+            g_cost: previous.g_cost + grid.move_cost(previous.position, position),
+            h_cost,
+            previous: None,
+            visits: previous.visits.clone(),
+            steps: previous.steps.map(|steps| steps + 1),
+            heading,
+        }
+    }
 
-        let offset_x = [-1, -1, 0, 1, 1, 1, 0, -1];
-        let offset_y = [0, -1, -1, -1, 0, 1, 1, 1];
+    /// Returns the search [`State`] of this node, two nodes with equal state are interchangeable.
+    pub(crate) fn state(&self) -> State {
+        State {
+            position: self.position,
+            visits: self.visits.clone(),
+            steps: self.steps,
+            heading: self.heading,
+        }
+    }
 
-        let pivot_x = self.position.x();
-        let pivot_y = self.position.y();
+    pub(crate) fn neighbours<T, H>(&self, grid: &T, heuristic: &H) -> Vec<Node>
+    where
+        T: Grid,
+        H: Fn(Position) -> usize,
+    {
+        let mut neighbours = vec![];
+        let capacities = grid.capacities();
+        let restricted = grid.restricts_turns();
 
-        for i in 0..8 {
-            let node_x = pivot_x + offset_x[i];
-            let node_y = pivot_y + offset_y[i];
-            let position = Position((node_x as usize, node_y as usize));
+        for position in grid.steps(self.position) {
+            let heading = restricted
+                .then(|| {
+                    let ((x, y), (px, py)) = (position.xy(), self.position.xy());
+                    Direction::from_offset((x - px, y - py))
+                })
+                .flatten();
+            if let (Some(from), Some(to)) = (self.heading, heading) {
+                if grid.forbids_turn(self.position, from, to) {
+                    continue;
+                }
+            }
+            let mut node = Node::new(position, self, grid, heuristic(position), heading);
 
-            if Node::is_valid((node_x, node_y), maze) {
-                let node = Node::new(position, self, maze.end.unwrap());
-                neighbours.push(node);
+            if let Some(index) = capacities
+                .iter()
+                .position(|(limited, _)| *limited == position)
+            {
+                if node.visits[index] >= capacities[index].1 {
+                    continue;
+                }
+                node.visits[index] += 1;
             }
+            neighbours.push(node);
         }
         neighbours
     }
 
+    /// Traces the path from the first [`Node`] to this one through `previous` nodes.
+    pub(crate) fn trace(self) -> VecDeque<(usize, usize)> {
+        let mut fields = VecDeque::from(vec![self.position.xy_usize()]);
+        let mut curr = self.previous;
+
+        while let Some(node) = curr.take() {
+            fields.push_front(node.position.xy_usize());
+            curr = node.previous;
+        }
+        fields
+    }
+
     pub(crate) fn lower_cost(&self, neighbour: &Node) -> bool {
         self.f_cost() < neighbour.f_cost()
             || (self.h_cost < neighbour.h_cost && self.f_cost() == neighbour.f_cost())
     }
 
-    pub(crate) fn heuristic(position: Position, end: Position) -> usize {
-        let a = (end.x() - position.x()).abs() * 10;
-        let b = (end.y() - position.y()).abs() * 10;
-        let c = a.pow(2) + b.pow(2);
-        (c as f64).sqrt() as usize
+    pub(crate) fn heuristic(position: Position, end: Position, heuristic: Heuristic) -> usize {
+        // Distances are taken before scaling, so `end` can lie on either side of `position`.
+        let a = end.x().abs_diff(position.x()) * 10;
+        let b = end.y().abs_diff(position.y()) * 10;
+        match heuristic {
+            Heuristic::Euclidean => {
+                let c = a.pow(2) + b.pow(2);
+                (c as f64).sqrt() as usize
+            }
+            Heuristic::Manhattan => a + b,
+            Heuristic::Chebyshev => a.max(b),
+            // Diagonal steps cover the shorter axis, each costs `4` more than a straight one.
+            Heuristic::Octile => a.max(b) + a.min(b) * 4 / 10,
+        }
     }
 
-    pub(crate) fn g_cost(position: Position, prev: &Node) -> usize {
-        let diagonal_positions = vec![
-            (prev.position.x() - 1, prev.position.y() - 1),
-            (prev.position.x() - 1, prev.position.y() + 1),
-            (prev.position.x() + 1, prev.position.y() - 1),
-            (prev.position.x() + 1, prev.position.y() + 1),
-        ];
+    /// Returns publicly exposed information about this node.
+    pub(crate) fn info(&self) -> NodeInfo {
+        NodeInfo {
+            position: self.position.xy_usize(),
+            g_cost: self.g_cost,
+            h_cost: self.h_cost,
+        }
+    }
 
-        if diagonal_positions.contains(&position.xy()) {
-            prev.g_cost + 14
+    /// Returns base cost of a single step between neighbouring positions, `14` if diagonal, `10` otherwise.
+    pub(crate) fn step_cost(from: Position, to: Position) -> usize {
+        if from.x() != to.x() && from.y() != to.y() {
+            14
         } else {
-            prev.g_cost + 10
+            10
         }
     }
 
     pub(crate) fn f_cost(&self) -> usize {
         self.g_cost + self.h_cost
     }
-
-    fn is_valid(position: (isize, isize), maze: &Maze) -> bool {
-        position.0 < maze.x_len() as isize
-            && position.0 >= 0
-            && position.1 < maze.y_len() as isize
-            && position.1 >= 0
-            && maze.field()[position.1 as usize][position.0 as usize] != maze.wall()
-    }
 }
 
 impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         self.position.0 == other.position.0
+            && self.visits == other.visits
+            && self.steps == other.steps
+            && self.heading == other.heading
     }
 }
 
@@ -100,9 +175,24 @@ impl Eq for Node {}
 impl Hash for Node {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.position.0.hash(state);
+        self.visits.hash(state);
+        self.steps.hash(state);
+        self.heading.hash(state);
     }
 }
 
+/// State of the search at a [`Node`], used for marking nodes as closed.
+///
+/// Besides the position it contains visit counts of capacity limited fields, the number
+/// of steps and the heading (if tracked), so the same field can be expanded again if it was reached differently.
+#[derive(Hash, PartialEq, Eq)]
+pub(crate) struct State {
+    position: Position,
+    visits: Vec<usize>,
+    steps: Option<usize>,
+    heading: Option<Direction>,
+}
+
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd)]
 pub(crate) struct Position(pub(crate) (usize, usize));
 
@@ -124,28 +214,141 @@ impl Position {
     }
 }
 
+/// Estimate of the remaining cost to the end used by the search, see [`Maze::set_heuristic`](crate::Maze::set_heuristic).
+///
+/// All of them are scaled by `10` like the step costs. With the default 8 directional movement `Euclidean`,
+/// `Chebyshev` and `Octile` never overestimate, so the found path is the shortest. `Octile` is exact on open
+/// ground and expands the fewest nodes. `Manhattan` counts a diagonal step as two straight ones, it is only
+/// admissible if the path can't move diagonally (see [`Movement::FourWay`](crate::Movement::FourWay)).
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Straight line distance. This is the default.
+    #[default]
+    Euclidean,
+    /// Sum of the distances along both axes.
+    Manhattan,
+    /// Longer of the distances along both axes.
+    Chebyshev,
+    /// Distance moving diagonally along the shorter axis and straight for the rest.
+    Octile,
+}
+
+impl Heuristic {
+    /// Returns estimated distance of `(x, y)` offset in fields, a straight step is `1.0` and diagonal `√2`.
+    pub(crate) fn distance(&self, (x, y): (f64, f64)) -> f64 {
+        let (x, y) = (x.abs(), y.abs());
+        match self {
+            Heuristic::Euclidean => x.hypot(y),
+            Heuristic::Manhattan => x + y,
+            Heuristic::Chebyshev => x.max(y),
+            Heuristic::Octile => x.max(y) + (std::f64::consts::SQRT_2 - 1.0) * x.min(y),
+        }
+    }
+}
+
+/// Custom ordering used to break ties between nodes with equal `f_cost`.
+pub(crate) type TieBreak = dyn Fn(&NodeInfo, &NodeInfo) -> Ordering;
+
+/// Costs and position of a node inside the open set, passed to the tie-break function set by
+/// [`Maze::set_tiebreak_fn`](crate::Maze::set_tiebreak_fn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeInfo {
+    position: (usize, usize),
+    g_cost: usize,
+    h_cost: usize,
+}
+
+impl NodeInfo {
+    /// Returns position `(x, y)` of the node.
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+
+    /// Returns cost of the path from start to the node.
+    pub fn g_cost(&self) -> usize {
+        self.g_cost
+    }
+
+    /// Returns estimated cost from the node to the end.
+    pub fn h_cost(&self) -> usize {
+        self.h_cost
+    }
+
+    /// Returns `g_cost + h_cost`.
+    pub fn f_cost(&self) -> usize {
+        self.g_cost + self.h_cost
+    }
+}
+
+/// Ways of breaking ties between open nodes with equal `f_cost`, applied in order.
+#[derive(Default, Clone, Copy)]
+pub(crate) struct Tiebreaks<'a> {
+    /// Prefer nodes closer to the straight line between `(start, goal)`.
+    pub(crate) cross: Option<(Position, Position)>,
+    /// Custom ordering set by the user.
+    pub(crate) custom: Option<&'a TieBreak>,
+}
+
 /// Wrapper around `f_cost` that represents priority inside the `PriorityQueue`.
 ///
 /// It has custom implementation of `PartialOrd` and `Ord` traits to provide correct functionality when getting
 /// popped out of a priority queue.
-pub(crate) struct Priority(pub(crate) usize);
+///
+/// Nodes with equal `f_cost` are ordered by [`Tiebreaks`], the one they order first is popped first.
+pub(crate) struct Priority<'a> {
+    pub(crate) info: NodeInfo,
+    /// Cross product measuring distance from the start-goal line, `0` if not used.
+    pub(crate) cross: usize,
+    pub(crate) tiebreak: Option<&'a TieBreak>,
+    /// Order in which the node was pushed to the open set, the latest one is popped first if all else is equal.
+    pub(crate) sequence: usize,
+}
+
+impl<'a> Priority<'a> {
+    pub(crate) fn new(node: &Node, tiebreaks: &Tiebreaks<'a>) -> Self {
+        let cross = match tiebreaks.cross {
+            Some((start, goal)) => {
+                let (dx1, dy1) = (node.position.x() - goal.x(), node.position.y() - goal.y());
+                let (dx2, dy2) = (start.x() - goal.x(), start.y() - goal.y());
+                (dx1 * dy2 - dx2 * dy1).unsigned_abs()
+            }
+            None => 0,
+        };
+
+        Priority {
+            info: node.info(),
+            cross,
+            tiebreak: tiebreaks.custom,
+            sequence: 0,
+        }
+    }
+}
 
-impl PartialEq for Priority {
+impl PartialEq for Priority<'_> {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl Eq for Priority {}
+impl Eq for Priority<'_> {}
 
-impl PartialOrd for Priority {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.0.partial_cmp(&self.0)
+impl PartialOrd for Priority<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Priority {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.0.cmp(&self.0)
+impl Ord for Priority<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .info
+            .f_cost()
+            .cmp(&self.info.f_cost())
+            .then_with(|| other.cross.cmp(&self.cross))
+            .then_with(|| match self.tiebreak {
+                Some(tiebreak) => tiebreak(&other.info, &self.info),
+                None => Ordering::Equal,
+            })
+            .then_with(|| self.sequence.cmp(&other.sequence))
     }
 }
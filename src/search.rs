@@ -0,0 +1,473 @@
+use crate::direction::Direction;
+use crate::node::{Heuristic, Node, Position, Priority, State, Tiebreaks};
+use priority_queue::PriorityQueue;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Grid that can be searched, it defines which fields are connected and at what cost.
+pub(crate) trait Grid {
+    /// Returns every walkable field that can be reached from `position` in a single step.
+    fn steps(&self, position: Position) -> Vec<Position>;
+
+    /// Returns the cost of a single step from `from` to the neighbouring `to`.
+    fn move_cost(&self, from: Position, to: Position) -> usize;
+
+    /// Returns fields that can only be entered a limited number of times along the path.
+    fn capacities(&self) -> &[(Position, usize)] {
+        &[]
+    }
+
+    /// Returns `true` if some turns are forbidden, the search then keeps track of the heading.
+    fn restricts_turns(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if stepping in direction `to` is forbidden at `at` after arriving in direction `from`.
+    fn forbids_turn(&self, _at: Position, _from: Direction, _to: Direction) -> bool {
+        false
+    }
+}
+
+/// Limits that a path has to satisfy, nodes exceeding them are pruned from the search.
+#[derive(Default, Clone, Copy)]
+pub(crate) struct Limits<'a> {
+    pub(crate) max_cost: Option<usize>,
+    /// Maximum number of fields on the path, including start and end.
+    pub(crate) max_steps: Option<usize>,
+    /// Flag stopping the search once it is set, checked before each expansion.
+    pub(crate) cancel: Option<&'a AtomicBool>,
+    /// Number of nodes expanded before the search pauses, see [`Outcome::Paused`].
+    pub(crate) budget: Option<usize>,
+    /// Called with the position of every node right before it is expanded.
+    pub(crate) on_expand: Option<&'a dyn Fn(Position)>,
+    /// Data structure holding the open set.
+    pub(crate) queue: QueueImpl,
+}
+
+impl Limits<'_> {
+    /// Returns `true` if the search was asked to stop.
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    fn exceeded(&self, node: &Node) -> bool {
+        self.max_cost
+            .is_some_and(|max_cost| node.f_cost() > max_cost)
+            || self
+                .max_steps
+                .zip(node.steps)
+                .is_some_and(|(max_steps, steps)| steps + 1 > max_steps)
+    }
+}
+
+/// Search algorithms that can be compared with [`Maze::bench_algorithms`](crate::Maze::bench_algorithms).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Algorithm {
+    /// A* guided by the maze heuristic.
+    AStar,
+    /// Dijkstra, same search without a heuristic.
+    Dijkstra,
+}
+
+impl Algorithm {
+    /// Returns weight the heuristic is multiplied by.
+    pub(crate) fn weight(&self) -> f64 {
+        match self {
+            Algorithm::AStar => 1.0,
+            Algorithm::Dijkstra => 0.0,
+        }
+    }
+}
+
+/// Data structure holding the open set of the search, see [`Maze::set_queue_impl`](crate::Maze::set_queue_impl).
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum QueueImpl {
+    /// Binary heap ordered by `f_cost` and tie-breaks.
+    #[default]
+    Binary,
+    /// Bucket for every `f_cost`, nodes are pushed in constant time and popped by scanning for the lowest bucket.
+    ///
+    /// Memory grows with the highest `f_cost`, so it only suits integer costs of a bounded size.
+    Bucket,
+}
+
+/// Statistics of a single search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Number of nodes popped from the open set and expanded.
+    pub expanded: usize,
+    /// Number of nodes pushed to the open set, including the start and improved nodes.
+    pub opened: usize,
+}
+
+/// Open and closed sets of a paused search, returned by [`Maze::solve_budgeted`](crate::Maze::solve_budgeted)
+/// so the search can be continued with [`Maze::resume`](crate::Maze::resume).
+pub struct SolveState {
+    open: Vec<Node>,
+    closed: HashSet<State>,
+    stats: SolveStats,
+}
+
+impl SolveState {
+    /// Creates state of a search that has only opened the `start` node.
+    pub(crate) fn new<T: Grid>(grid: &T, start: Position, h_cost: usize, limits: &Limits) -> Self {
+        SolveState {
+            open: vec![Node::start(start, grid, h_cost, limits.max_steps.is_some())],
+            closed: HashSet::new(),
+            stats: SolveStats {
+                expanded: 0,
+                opened: 1,
+            },
+        }
+    }
+
+    /// Returns statistics of the search so far.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
+    }
+}
+
+impl Debug for SolveState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolveState")
+            .field("open", &self.open.len())
+            .field("closed", &self.closed.len())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+/// Progress of a search split into multiple calls, see [`Maze::solve_budgeted`](crate::Maze::solve_budgeted).
+#[derive(Debug)]
+pub enum SolveProgress {
+    /// Maze is solved, contains the shortest path as `(x, y)` coordinates.
+    Done(Vec<(usize, usize)>),
+    /// Every reachable field was expanded without reaching the end.
+    Unsolvable,
+    /// Budget ran out before the search finished, pass the state to `resume` to continue.
+    InProgress(SolveState),
+}
+
+/// Result of a search that can run out of its [`Limits::budget`].
+pub(crate) enum Outcome<T = Node> {
+    /// Reached goal node, its path can be traced back through `previous`.
+    Found(T),
+    /// Every reachable node was expanded (or the search was cancelled) without reaching the goal.
+    Exhausted,
+    /// Budget ran out, search can be continued from the state.
+    Paused(SolveState),
+}
+
+impl<T> Outcome<T> {
+    /// Maps the reached goal with `f`, leaving other outcomes as they are.
+    pub(crate) fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        match self {
+            Outcome::Found(goal) => Outcome::Found(f(goal)),
+            Outcome::Exhausted => Outcome::Exhausted,
+            Outcome::Paused(state) => Outcome::Paused(state),
+        }
+    }
+}
+
+/// Runs A* over the `grid` from `start` until a position satisfying `is_goal` is reached.
+///
+/// `heuristic` must not overestimate the cost to the nearest goal for the found path to be the shortest.
+/// Returns the reached goal [`Node`], its path can be traced back through `previous`.
+pub(crate) fn search<T, G, H>(
+    grid: &T,
+    start: Position,
+    is_goal: G,
+    heuristic: H,
+    limits: &Limits,
+    tiebreaks: &Tiebreaks,
+) -> (Option<Node>, SolveStats)
+where
+    T: Grid,
+    G: Fn(Position) -> bool,
+    H: Fn(Position) -> usize,
+{
+    let state = SolveState::new(grid, start, heuristic(start), limits);
+    match resume(grid, state, is_goal, heuristic, limits, tiebreaks) {
+        (Outcome::Found(goal), stats) => (Some(goal), stats),
+        (_, stats) => (None, stats),
+    }
+}
+
+/// Continues A* over the `grid` from `state` until a position satisfying `is_goal` is reached,
+/// or until `budget` of the `limits` runs out.
+///
+/// `grid`, `is_goal` and `heuristic` must be the same as in the search that produced `state`.
+pub(crate) fn resume<T, G, H>(
+    grid: &T,
+    state: SolveState,
+    is_goal: G,
+    heuristic: H,
+    limits: &Limits,
+    tiebreaks: &Tiebreaks,
+) -> (Outcome, SolveStats)
+where
+    T: Grid,
+    G: Fn(Position) -> bool,
+    H: Fn(Position) -> usize,
+{
+    let SolveState {
+        open,
+        mut closed,
+        mut stats,
+    } = state;
+    let pause_at = limits.budget.map(|budget| stats.expanded + budget);
+
+    let mut open = OpenSet::new(limits.queue, open, tiebreaks);
+
+    while let Some((current, priority)) = open.pop() {
+        if limits.cancelled() {
+            break;
+        }
+        if is_goal(current.position) {
+            return (Outcome::Found(current), stats);
+        }
+        if pause_at == Some(stats.expanded) {
+            open.push(current, priority);
+            let state = SolveState {
+                open: open.into_vec(),
+                closed,
+                stats,
+            };
+            return (Outcome::Paused(state), stats);
+        }
+        if let Some(on_expand) = limits.on_expand {
+            on_expand(current.position);
+        }
+        stats.expanded += 1;
+        for mut neighbour in current.neighbours(grid, &heuristic) {
+            if closed.contains(&neighbour.state()) || limits.exceeded(&neighbour) {
+                continue;
+            }
+            if let Some(node) = open.get(&neighbour) {
+                if !neighbour.lower_cost(node) {
+                    continue;
+                }
+                // `push` only updates the priority of an equal item, keeping its stale
+                // `previous` and `g_cost`, so the old node has to be removed first.
+                open.remove(&neighbour);
+            }
+            neighbour.previous = Some(Box::new(current.clone()));
+            let priority = Priority::new(&neighbour, tiebreaks);
+            open.push(neighbour, priority);
+            stats.opened += 1;
+        }
+        closed.insert(current.state());
+    }
+    (Outcome::Exhausted, stats)
+}
+
+/// Open set of the search, implemented as chosen by [`QueueImpl`].
+///
+/// Both implementations order nodes the same way, ties are broken by the order nodes were pushed in.
+struct OpenSet<'a> {
+    queue: Queue<'a>,
+    /// Number of nodes pushed so far, used as the [`Priority::sequence`] of the next one.
+    pushed: usize,
+}
+
+enum Queue<'a> {
+    Binary(PriorityQueue<Node, Priority<'a>>),
+    Bucket(BucketQueue<'a>),
+}
+
+impl<'a> OpenSet<'a> {
+    /// Creates open set of type `queue` holding `nodes`.
+    fn new(queue: QueueImpl, nodes: Vec<Node>, tiebreaks: &Tiebreaks<'a>) -> Self {
+        let queue = match queue {
+            QueueImpl::Binary => Queue::Binary(PriorityQueue::new()),
+            QueueImpl::Bucket => Queue::Bucket(BucketQueue::default()),
+        };
+        let mut open = OpenSet { queue, pushed: 0 };
+        for node in nodes {
+            let priority = Priority::new(&node, tiebreaks);
+            open.push(node, priority);
+        }
+        open
+    }
+
+    fn push(&mut self, node: Node, mut priority: Priority<'a>) {
+        priority.sequence = self.pushed;
+        self.pushed += 1;
+        match &mut self.queue {
+            Queue::Binary(queue) => {
+                queue.push(node, priority);
+            }
+            Queue::Bucket(queue) => queue.push(node, priority),
+        }
+    }
+
+    /// Removes and returns the node with the highest priority.
+    fn pop(&mut self) -> Option<(Node, Priority<'a>)> {
+        match &mut self.queue {
+            Queue::Binary(queue) => queue.pop(),
+            Queue::Bucket(queue) => queue.pop(),
+        }
+    }
+
+    /// Returns the queued node equal to `node`.
+    fn get(&self, node: &Node) -> Option<&Node> {
+        match &self.queue {
+            Queue::Binary(queue) => queue.get(node).map(|(node, _)| node),
+            Queue::Bucket(queue) => queue.get(node),
+        }
+    }
+
+    fn remove(&mut self, node: &Node) {
+        match &mut self.queue {
+            Queue::Binary(queue) => {
+                queue.remove(node);
+            }
+            Queue::Bucket(queue) => queue.remove(node),
+        }
+    }
+
+    fn into_vec(self) -> Vec<Node> {
+        match self.queue {
+            Queue::Binary(queue) => queue.into_vec(),
+            Queue::Bucket(queue) => queue.into_vec(),
+        }
+    }
+}
+
+/// Priority queue with a bucket for every `f_cost`.
+///
+/// Nodes inside the lowest bucket are ordered by their [`Priority`], so ties are broken the same way as in the heap.
+#[derive(Default)]
+struct BucketQueue<'a> {
+    buckets: Vec<Vec<(Node, Priority<'a>)>>,
+    /// Index of the lowest bucket that may be non-empty.
+    lowest: usize,
+    /// `f_cost` (bucket index) of every queued node.
+    costs: HashMap<State, usize>,
+}
+
+impl<'a> BucketQueue<'a> {
+    fn push(&mut self, node: Node, priority: Priority<'a>) {
+        let f_cost = node.f_cost();
+        if self.buckets.len() <= f_cost {
+            self.buckets.resize_with(f_cost + 1, Vec::new);
+        }
+        self.costs.insert(node.state(), f_cost);
+        self.buckets[f_cost].push((node, priority));
+        self.lowest = self.lowest.min(f_cost);
+    }
+
+    fn pop(&mut self) -> Option<(Node, Priority<'a>)> {
+        while self.buckets.get(self.lowest)?.is_empty() {
+            self.lowest += 1;
+        }
+        let bucket = &mut self.buckets[self.lowest];
+        let index = (0..bucket.len()).max_by(|&a, &b| bucket[a].1.cmp(&bucket[b].1))?;
+        let (node, priority) = bucket.swap_remove(index);
+        self.costs.remove(&node.state());
+        Some((node, priority))
+    }
+
+    fn get(&self, node: &Node) -> Option<&Node> {
+        let f_cost = *self.costs.get(&node.state())?;
+        self.buckets[f_cost]
+            .iter()
+            .map(|(queued, _)| queued)
+            .find(|queued| *queued == node)
+    }
+
+    fn remove(&mut self, node: &Node) {
+        if let Some(f_cost) = self.costs.remove(&node.state()) {
+            self.buckets[f_cost].retain(|(queued, _)| queued != node);
+        }
+    }
+
+    fn into_vec(self) -> Vec<Node> {
+        self.buckets
+            .into_iter()
+            .flatten()
+            .map(|(node, _)| node)
+            .collect()
+    }
+}
+
+/// Boolean walkability grid searched by [`astar_grid`], `true` fields are walkable.
+struct BoolGrid<'a> {
+    fields: &'a [Vec<bool>],
+    diagonal: bool,
+}
+
+impl Grid for BoolGrid<'_> {
+    fn steps(&self, position: Position) -> Vec<Position> {
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| self.diagonal || !direction.is_diagonal())
+            .filter_map(|direction| {
+                let (dx, dy) = direction.offset();
+                let x = position.0 .0.checked_add_signed(dx)?;
+                let y = position.0 .1.checked_add_signed(dy)?;
+                let walkable = *self.fields.get(y)?.get(x)?;
+                walkable.then_some(Position((x, y)))
+            })
+            .collect()
+    }
+
+    fn move_cost(&self, from: Position, to: Position) -> usize {
+        Node::step_cost(from, to)
+    }
+}
+
+/// Finds the shortest path between `start` and `end` (`(x, y)`) on a grid of walkable (`true`) fields.
+///
+/// Rows are indexed by `y`, straight steps cost `10` and diagonal `14`, diagonal steps are only taken if `diagonal`.
+/// Returns [`None`] if either position is outside of the grid or not walkable, or if `end` can't be reached.
+///
+/// # Example
+/// ```
+/// use astar::astar_grid;
+///
+/// let grid = vec![
+///     vec![true, true, true],
+///     vec![false, false, true],
+///     vec![true, true, true],
+/// ];
+///
+/// let path = astar_grid(&grid, (0, 0), (0, 2), true).unwrap();
+/// assert_eq!(path, vec![(0, 0), (1, 0), (2, 1), (1, 2), (0, 2)]);
+///
+/// let path = astar_grid(&grid, (0, 0), (0, 2), false).unwrap();
+/// assert_eq!(path.len(), 7);
+///
+/// let walled = vec![vec![true, false, true]];
+/// assert_eq!(astar_grid(&walled, (0, 0), (2, 0), true), None);
+/// assert_eq!(astar_grid(&walled, (0, 0), (2, 0), false), None);
+/// ```
+pub fn astar_grid(
+    grid: &[Vec<bool>],
+    start: (usize, usize),
+    end: (usize, usize),
+    diagonal: bool,
+) -> Option<Vec<(usize, usize)>> {
+    let walkable = |(x, y): (usize, usize)| grid.get(y).and_then(|row| row.get(x)) == Some(&true);
+    if !walkable(start) || !walkable(end) {
+        return None;
+    }
+
+    let grid = BoolGrid {
+        fields: grid,
+        diagonal,
+    };
+    let end = Position(end);
+    let (goal, _) = search(
+        &grid,
+        Position(start),
+        |position| position == end,
+        |position| Node::heuristic(position, end, Heuristic::Euclidean),
+        &Limits::default(),
+        &Tiebreaks::default(),
+    );
+
+    goal.map(|goal| goal.trace().into())
+}
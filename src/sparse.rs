@@ -0,0 +1,271 @@
+use crate::direction::{Direction, Movement};
+use crate::error::{Error, ErrorKind::*};
+use crate::maze::Result;
+use crate::node::{Heuristic, Node, Position, Tiebreaks};
+use crate::search::{self, Grid, Limits};
+use std::collections::HashMap;
+use std::default::Default;
+
+/// [`SparseMaze`] is an alternative to [`crate::Maze`] for huge maps that are mostly walls.
+///
+/// Only walkable fields are stored (in a [`HashMap`]), every position that is not stored is a wall.
+/// It is solved by the same **A*** search, using 8 directions of movement by default.
+pub struct SparseMaze {
+    fields: HashMap<Position, char>,
+    start: Option<Position>,
+    end: Option<Position>,
+    path: Option<Vec<(usize, usize)>>,
+    start_char: char,
+    end_char: char,
+    wall_char: char,
+    movement: Movement,
+    heuristic: Heuristic,
+}
+
+impl SparseMaze {
+    /// Constructs a new empty [`SparseMaze`], every position is a wall until a field is inserted.
+    ///
+    /// Start is marked by `'S'` and end by `'E'` by default, inserted fields holding
+    /// the wall symbol (`'W'` by default) are walls as well.
+    pub fn new() -> Self {
+        SparseMaze {
+            fields: HashMap::new(),
+            start: None,
+            end: None,
+            path: None,
+            start_char: 'S',
+            end_char: 'E',
+            wall_char: 'W',
+            movement: Movement::default(),
+            heuristic: Heuristic::default(),
+        }
+    }
+
+    /// Inserts field with `symbol` at `position` (`(x, y)`), invalidating the current path.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::SparseMaze;
+    ///
+    /// // Start is the first (row by row) field holding its symbol, wherever it was inserted.
+    /// let mut maze = SparseMaze::new();
+    /// maze.insert((3, 1), 'S');
+    /// maze.insert((1, 1), 'S');
+    /// maze.insert((2, 0), '.');
+    /// maze.insert((4, 0), 'E');
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(1, 1), (2, 0), (3, 1), (4, 0)]);
+    ///
+    /// maze.remove((1, 1));
+    /// maze.try_solve().unwrap();
+    /// assert_eq!(maze.get_path().unwrap(), vec![(3, 1), (4, 0)]);
+    /// ```
+    pub fn insert(&mut self, position: (usize, usize), symbol: char) {
+        let position = Position(position);
+        self.fields.insert(position, symbol);
+
+        if self.is_endpoint(position) {
+            self.update_endpoints();
+        } else {
+            self.update_endpoints_at(position, symbol);
+        }
+    }
+
+    /// Removes field at `position` (`(x, y)`) turning it into a wall, returns its symbol if it was stored.
+    pub fn remove(&mut self, position: (usize, usize)) -> Option<char> {
+        let position = Position(position);
+        let symbol = self.fields.remove(&position);
+
+        if self.is_endpoint(position) {
+            self.update_endpoints();
+        } else {
+            self.path = None;
+        }
+        symbol
+    }
+
+    /// Sets the symbol of start field.
+    pub fn set_start_char(mut self, symbol: char) -> Self {
+        self.start_char = symbol;
+        self.update_endpoints();
+        self
+    }
+
+    /// Sets the symbol of end field.
+    pub fn set_end_char(mut self, symbol: char) -> Self {
+        self.end_char = symbol;
+        self.update_endpoints();
+        self
+    }
+
+    /// Sets the symbol of walls.
+    pub fn set_walls_char(mut self, symbol: char) -> Self {
+        self.wall_char = symbol;
+        self.path = None;
+        self
+    }
+
+    /// Sets directions the path can move in, [`Movement::EightWay`] by default. Invalidates the current path.
+    pub fn set_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self.path = None;
+        self
+    }
+
+    /// Sets heuristic estimating the remaining cost of the path, [`Heuristic::Euclidean`] by default.
+    ///
+    /// See [`Heuristic`] for which of them keep the found path the shortest.
+    pub fn set_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self.path = None;
+        self
+    }
+
+    /// Returns number of stored fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if there are no stored fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Sets the path in our maze (tries to solve the maze).
+    ///
+    /// # Errors
+    /// If symbols for `start`/`end` are not inserted.
+    ///
+    /// If it is impossible to solve the maze.
+    ///
+    /// If `start`, `end` or `wall` share the same character.
+    ///
+    /// # Example
+    /// ```
+    /// use astar::{Movement, SparseMaze};
+    ///
+    /// // Thin corridor on a huge map, every other position is a wall.
+    /// let mut maze = SparseMaze::new();
+    /// maze.insert((1_000_000, 1_000_000), 'S');
+    /// maze.insert((1_000_001, 1_000_001), '.');
+    /// maze.insert((1_000_002, 1_000_001), '.');
+    /// maze.insert((1_000_003, 1_000_001), 'E');
+    /// maze.try_solve().unwrap();
+    ///
+    /// let path = maze.get_path().unwrap();
+    /// assert_eq!(path.len(), 4);
+    /// assert_eq!(path[1], (1_000_001, 1_000_001));
+    ///
+    /// // Corridor bends diagonally, so it can't be passed moving in four directions.
+    /// let mut maze = maze.set_movement(Movement::FourWay);
+    /// assert!(maze.try_solve().is_err());
+    /// ```
+    pub fn try_solve(&mut self) -> Result<()> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if self.start_char == self.end_char
+                || self.wall_char == self.start_char
+                || self.wall_char == self.end_char
+            {
+                return Err(InvalidCharacters.into());
+            }
+
+            let (goal, _) = search::search(
+                self,
+                start,
+                |position| position == end,
+                |position| Node::heuristic(position, end, self.heuristic),
+                &Limits::default(),
+                &Tiebreaks::default(),
+            );
+            let goal = goal.ok_or(Error::from(MazeIsNotSolvable))?;
+
+            self.path = Some(goal.trace().into());
+            Ok(())
+        } else {
+            Err(StartEndNotSet.into())
+        }
+    }
+
+    /// Returns [`Vec`] that represents the shortest path from `Start` to the `End`.
+    ///
+    /// # Errors
+    /// If [`SparseMaze`] is not solved.
+    pub fn get_path(&self) -> Result<Vec<(usize, usize)>> {
+        self.path.clone().ok_or(MazeNotSolved.into())
+    }
+
+    /// Helper function returning `true` if the field at `position` can be entered.
+    fn is_walkable(&self, position: Position) -> bool {
+        self.fields
+            .get(&position)
+            .is_some_and(|symbol| *symbol != self.wall_char)
+    }
+
+    /// Helper function returning `true` if `position` is the current start or end.
+    fn is_endpoint(&self, position: Position) -> bool {
+        self.start == Some(position) || self.end == Some(position)
+    }
+
+    /// Helper function moving start or end to `position` if it holds their symbol and comes first (row by row),
+    /// so inserting a field doesn't require a scan of every stored field.
+    fn update_endpoints_at(&mut self, position: Position, symbol: char) {
+        let row_major = |position: Position| (position.0 .1, position.0 .0);
+        let first = |current: Option<Position>| {
+            current.is_none_or(|current| row_major(position) < row_major(current))
+        };
+
+        if symbol == self.start_char && first(self.start) {
+            self.start = Some(position);
+        }
+        if symbol == self.end_char && first(self.end) {
+            self.end = Some(position);
+        }
+        self.path = None;
+    }
+
+    /// Helper function setting start and end to the first (row by row) field holding their symbol.
+    fn update_endpoints(&mut self) {
+        let first = |symbol: char| {
+            self.fields
+                .iter()
+                .filter(|(_, char)| **char == symbol)
+                .map(|(position, _)| *position)
+                .min_by_key(|position| (position.0 .1, position.0 .0))
+        };
+
+        self.start = first(self.start_char);
+        self.end = first(self.end_char);
+        self.path = None;
+    }
+}
+
+impl Grid for SparseMaze {
+    fn steps(&self, position: Position) -> Vec<Position> {
+        let (x, y) = position.xy();
+
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| self.movement.allows(*direction))
+            .filter_map(|direction| {
+                let (offset_x, offset_y) = direction.offset();
+                let (x, y) = (x + offset_x, y + offset_y);
+
+                if x < 0 || y < 0 {
+                    return None;
+                }
+                let neighbour = Position((x as usize, y as usize));
+                self.is_walkable(neighbour).then_some(neighbour)
+            })
+            .collect()
+    }
+
+    fn move_cost(&self, from: Position, to: Position) -> usize {
+        Node::step_cost(from, to)
+    }
+}
+
+impl Default for SparseMaze {
+    fn default() -> Self {
+        Self::new()
+    }
+}